@@ -1,30 +1,613 @@
 use anyhow::anyhow;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
 use std::str::FromStr;
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Config {
+    #[serde(default = "default_lang")]
     pub lang: String,
+    /// Singularize table names when deriving class names (`users` -> `User`).
+    #[serde(default)]
+    pub singularize: bool,
+    /// Explicit class names keyed by table name. These win over `singularize`.
+    #[serde(default)]
+    pub class_names: BTreeMap<String, String>,
+    /// How the schema of a qualified table name (`billing.invoices`) is
+    /// represented: `namespace` or `prefix`.
+    #[serde(default = "default_schema_mode")]
+    pub schema_mode: String,
+    /// Generate constructor guards that reject values longer than the column
+    /// length or negative values in unsigned columns.
+    #[serde(default)]
+    pub guards: bool,
+    /// The database the generated code targets: `generic`, `mysql`,
+    /// `postgres`, `sqlite` or `mssql`.
+    #[serde(default = "default_dialect")]
+    pub dialect: String,
+    /// Generate a PDO repository class next to each model.
+    #[serde(default)]
+    pub repositories: bool,
+    #[serde(default)]
+    pub php: PhpConfig,
+    #[serde(default)]
+    pub typescript: TypeScriptConfig,
+    #[serde(default)]
+    pub rust: RustConfig,
+    #[serde(default)]
+    pub go: GoConfig,
+    #[serde(default)]
+    pub python: PythonConfig,
+    #[serde(default)]
+    pub jvm: JvmConfig,
+    #[serde(default)]
+    pub csharp: CSharpConfig,
+    #[serde(default)]
+    pub openapi: OpenApiConfig,
+    /// Outputs generated in one run, each overriding the options above. Without
+    /// any, `lang` is generated into `models/`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub targets: Vec<Target>,
+}
+
+/// One `[[targets]]` entry.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Target {
+    pub lang: String,
+    /// The directory the files are written to, relative to the working
+    /// directory.
+    #[serde(default = "default_output")]
+    pub output: PathBuf,
+    /// The namespace or package of the generated code, for the languages that
+    /// have one.
+    pub namespace: Option<String>,
+    /// Any other option, e.g. `guards = true` or `[targets.typescript]`.
+    #[serde(flatten)]
+    pub options: toml::Table,
+}
+
+/// Options for `lang = "php"`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PhpConfig {
+    /// The root namespace of the generated classes.
+    #[serde(default = "default_php_namespace")]
+    pub namespace: String,
+    /// The kind of model generated: `plain` classes, Laravel `eloquent`
+    /// models or `doctrine` entities.
+    #[serde(default = "default_php_flavor")]
+    pub flavor: String,
+}
+
+impl Default for PhpConfig {
+    fn default() -> Self {
+        Self {
+            namespace: default_php_namespace(),
+            flavor: default_php_flavor(),
+        }
+    }
+}
+
+/// Options for `lang = "typescript"`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TypeScriptConfig {
+    /// Declare rows as an `interface` or a `type` alias.
+    #[serde(default = "default_declaration")]
+    pub declaration: String,
+    /// Type date and time columns as `string`, as they arrive in JSON, or
+    /// `Date`.
+    #[serde(default = "default_dates")]
+    pub dates: String,
+}
+
+impl Default for TypeScriptConfig {
+    fn default() -> Self {
+        Self {
+            declaration: default_declaration(),
+            dates: default_dates(),
+        }
+    }
+}
+
+/// Options for `lang = "rust"`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RustConfig {
+    /// The crate date and time columns are typed with: `chrono` or `time`.
+    #[serde(default = "default_date_crate")]
+    pub dates: String,
+    /// Derive `serde::Serialize` and `serde::Deserialize`.
+    #[serde(default)]
+    pub serde: bool,
+    /// Derive `sqlx::FromRow`.
+    #[serde(default)]
+    pub sqlx: bool,
+}
+
+impl Default for RustConfig {
+    fn default() -> Self {
+        Self {
+            dates: default_date_crate(),
+            serde: false,
+            sqlx: false,
+        }
+    }
+}
+
+/// Options for `lang = "go"`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GoConfig {
+    /// The package of the generated files.
+    #[serde(default = "default_package")]
+    pub package: String,
+    /// How nullable columns are typed: `pointer` (`*string`) or `sql`
+    /// (`sql.NullString`).
+    #[serde(default = "default_nulls")]
+    pub nulls: String,
+}
+
+impl Default for GoConfig {
+    fn default() -> Self {
+        Self {
+            package: default_package(),
+            nulls: default_nulls(),
+        }
+    }
+}
+
+/// Options for `lang = "python"`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PythonConfig {
+    /// Generate `dataclass` classes or Pydantic `BaseModel`s (`pydantic`).
+    #[serde(default = "default_python_style")]
+    pub style: String,
+}
+
+impl Default for PythonConfig {
+    fn default() -> Self {
+        Self {
+            style: default_python_style(),
+        }
+    }
+}
+
+/// Options for `lang = "kotlin"` and `lang = "java"`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct JvmConfig {
+    /// The package of the generated classes.
+    #[serde(default = "default_package")]
+    pub package: String,
+    /// Annotate the classes as JPA entities.
+    #[serde(default)]
+    pub jpa: bool,
+}
+
+impl Default for JvmConfig {
+    fn default() -> Self {
+        Self {
+            package: default_package(),
+            jpa: false,
+        }
+    }
+}
+
+/// Options for `lang = "csharp"`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CSharpConfig {
+    /// The namespace of the generated types.
+    #[serde(default = "default_csharp_namespace")]
+    pub namespace: String,
+    /// Generate positional `record`s or `class`es with properties.
+    #[serde(default = "default_csharp_style")]
+    pub style: String,
+    /// Mapping attributes to add: `none`, `dapper` (Dapper.Contrib) or
+    /// `efcore` (data annotations).
+    #[serde(default = "default_csharp_attributes")]
+    pub attributes: String,
+}
+
+impl Default for CSharpConfig {
+    fn default() -> Self {
+        Self {
+            namespace: default_csharp_namespace(),
+            style: default_csharp_style(),
+            attributes: default_csharp_attributes(),
+        }
+    }
+}
+
+/// Options for `lang = "openapi"`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OpenApiConfig {
+    /// `info.title` of the generated document.
+    #[serde(default = "default_openapi_title")]
+    pub title: String,
+    /// `info.version` of the generated document.
+    #[serde(default = "default_openapi_version")]
+    pub version: String,
+}
+
+impl Default for OpenApiConfig {
+    fn default() -> Self {
+        Self {
+            title: default_openapi_title(),
+            version: default_openapi_version(),
+        }
+    }
+}
+
+fn default_php_namespace() -> String {
+    String::from("Quaff")
+}
+
+fn default_php_flavor() -> String {
+    String::from("plain")
+}
+
+fn default_openapi_title() -> String {
+    String::from("Models")
+}
+
+fn default_openapi_version() -> String {
+    String::from("1.0.0")
+}
+
+fn default_csharp_namespace() -> String {
+    String::from("Quaff.Models")
+}
+
+fn default_csharp_style() -> String {
+    String::from("record")
+}
+
+fn default_csharp_attributes() -> String {
+    String::from("none")
+}
+
+fn default_python_style() -> String {
+    String::from("dataclass")
+}
+
+fn default_package() -> String {
+    String::from("models")
+}
+
+fn default_nulls() -> String {
+    String::from("pointer")
+}
+
+fn default_date_crate() -> String {
+    String::from("chrono")
+}
+
+fn default_declaration() -> String {
+    String::from("interface")
+}
+
+fn default_dates() -> String {
+    String::from("string")
+}
+
+fn default_lang() -> String {
+    String::from("PHP")
+}
+
+fn default_output() -> PathBuf {
+    PathBuf::from("models")
+}
+
+fn default_dialect() -> String {
+    String::from("generic")
+}
+
+fn default_schema_mode() -> String {
+    String::from("namespace")
+}
+
+impl Config {
+    /// The configs of everything one run generates, with the directory each
+    /// is written to.
+    pub fn targets(&self) -> anyhow::Result<Vec<(Config, PathBuf)>> {
+        if self.targets.is_empty() {
+            return Ok(vec![(self.clone(), default_output())]);
+        }
+
+        let toml::Value::Table(mut base) = toml::Value::try_from(self)? else {
+            unreachable!("a struct serializes to a table");
+        };
+        base.remove("targets");
+
+        self.targets
+            .iter()
+            .map(|target| {
+                let mut table = base.clone();
+                merge(&mut table, target.options.clone());
+                table.insert(String::from("lang"), target.lang.clone().into());
+
+                if let Some(namespace) = &target.namespace {
+                    let (section, key) = match LangOption::from_str(&target.lang)? {
+                        LangOption::Php => ("php", "namespace"),
+                        LangOption::Go => ("go", "package"),
+                        LangOption::Kotlin | LangOption::Java => ("jvm", "package"),
+                        LangOption::CSharp => ("csharp", "namespace"),
+                        _ => return Err(anyhow!("{} has no namespace or package", target.lang)),
+                    };
+                    if let Some(toml::Value::Table(section)) = table.get_mut(section) {
+                        section.insert(String::from(key), namespace.clone().into());
+                    }
+                }
+
+                Ok((toml::Value::Table(table).try_into()?, target.output.clone()))
+            })
+            .collect()
+    }
+
+    /// The class naming options, parsed.
+    pub fn class_names(&self) -> anyhow::Result<ClassNames> {
+        Ok(ClassNames {
+            overrides: self.class_names.clone(),
+            singularize: self.singularize,
+            schema_mode: SchemaMode::from_str(&self.schema_mode)?,
+        })
+    }
+
+    /// Resolves the generated class name for `table` in `schema`. Overrides may
+    /// be keyed by either the qualified or the bare table name.
+    pub fn class_name(&self, schema: &[String], table: &str) -> anyhow::Result<String> {
+        Ok(self.class_names()?.class_name(schema, table))
+    }
+}
+
+/// The options that turn table names into class names.
+#[derive(Debug, Clone)]
+pub struct ClassNames {
+    overrides: BTreeMap<String, String>,
+    singularize: bool,
+    schema_mode: SchemaMode,
+}
+
+impl ClassNames {
+    /// See [`Config::class_name`].
+    pub fn class_name(&self, schema: &[String], table: &str) -> String {
+        let qualified = schema
+            .iter()
+            .map(String::as_str)
+            .chain([table])
+            .collect::<Vec<_>>()
+            .join(".");
+
+        if let Some(name) = self
+            .overrides
+            .get(&qualified)
+            .or_else(|| self.overrides.get(table))
+        {
+            return name.clone();
+        }
+
+        let name = crate::lang::class_name(table, self.singularize);
+        match self.schema_mode {
+            SchemaMode::Namespace => name,
+            SchemaMode::Prefix => format!(
+                "{}{name}",
+                crate::lang::class_name(&schema.join("_"), false)
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum SchemaMode {
+    Namespace,
+    Prefix,
+}
+
+impl FromStr for SchemaMode {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "namespace" => Ok(Self::Namespace),
+            "prefix" => Ok(Self::Prefix),
+            _ => Err(anyhow!("invalid schema mode: {s}")),
+        }
+    }
 }
 
 pub enum LangOption {
-    PHP,
+    Php,
+    TypeScript,
+    Rust,
+    Go,
+    Python,
+    Kotlin,
+    Java,
+    CSharp,
+    JsonSchema,
+    OpenApi,
 }
 
 impl FromStr for LangOption {
     type Err = anyhow::Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
-            "php" => Ok(Self::PHP),
+            "php" => Ok(Self::Php),
+            "typescript" | "ts" => Ok(Self::TypeScript),
+            "rust" | "rs" => Ok(Self::Rust),
+            "go" | "golang" => Ok(Self::Go),
+            "python" | "py" => Ok(Self::Python),
+            "kotlin" | "kt" => Ok(Self::Kotlin),
+            "java" => Ok(Self::Java),
+            "csharp" | "c#" | "cs" => Ok(Self::CSharp),
+            "jsonschema" | "json-schema" => Ok(Self::JsonSchema),
+            "openapi" => Ok(Self::OpenApi),
             _ => Err(anyhow!("cannot convert string to language value")),
         }
     }
 }
 
+pub enum PhpFlavor {
+    Plain,
+    Eloquent,
+    Doctrine,
+}
+
+impl FromStr for PhpFlavor {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "plain" => Ok(Self::Plain),
+            "eloquent" | "laravel" => Ok(Self::Eloquent),
+            "doctrine" => Ok(Self::Doctrine),
+            _ => Err(anyhow!("invalid php flavor: {s}")),
+        }
+    }
+}
+
+pub enum Declaration {
+    Interface,
+    Type,
+}
+
+impl FromStr for Declaration {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "interface" => Ok(Self::Interface),
+            "type" => Ok(Self::Type),
+            _ => Err(anyhow!("invalid typescript declaration: {s}")),
+        }
+    }
+}
+
+pub enum DateRepresentation {
+    String,
+    Date,
+}
+
+impl FromStr for DateRepresentation {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "string" => Ok(Self::String),
+            "date" => Ok(Self::Date),
+            _ => Err(anyhow!("invalid typescript date representation: {s}")),
+        }
+    }
+}
+
+pub enum DateCrate {
+    Chrono,
+    Time,
+}
+
+impl FromStr for DateCrate {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "chrono" => Ok(Self::Chrono),
+            "time" => Ok(Self::Time),
+            _ => Err(anyhow!("invalid rust date crate: {s}")),
+        }
+    }
+}
+
+pub enum NullStyle {
+    Pointer,
+    Sql,
+}
+
+impl FromStr for NullStyle {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "pointer" => Ok(Self::Pointer),
+            "sql" => Ok(Self::Sql),
+            _ => Err(anyhow!("invalid go null style: {s}")),
+        }
+    }
+}
+
+pub enum PythonStyle {
+    Dataclass,
+    Pydantic,
+}
+
+impl FromStr for PythonStyle {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "dataclass" => Ok(Self::Dataclass),
+            "pydantic" => Ok(Self::Pydantic),
+            _ => Err(anyhow!("invalid python style: {s}")),
+        }
+    }
+}
+
+pub enum CSharpStyle {
+    Record,
+    Class,
+}
+
+impl FromStr for CSharpStyle {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "record" => Ok(Self::Record),
+            "class" => Ok(Self::Class),
+            _ => Err(anyhow!("invalid csharp style: {s}")),
+        }
+    }
+}
+
+pub enum CSharpAttributes {
+    None,
+    Dapper,
+    EfCore,
+}
+
+impl FromStr for CSharpAttributes {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(Self::None),
+            "dapper" => Ok(Self::Dapper),
+            "efcore" | "ef" => Ok(Self::EfCore),
+            _ => Err(anyhow!("invalid csharp attributes: {s}")),
+        }
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
-            lang: String::from("PHP"),
+            lang: default_lang(),
+            singularize: false,
+            class_names: BTreeMap::new(),
+            schema_mode: default_schema_mode(),
+            guards: false,
+            dialect: default_dialect(),
+            repositories: false,
+            php: PhpConfig::default(),
+            typescript: TypeScriptConfig::default(),
+            rust: RustConfig::default(),
+            go: GoConfig::default(),
+            python: PythonConfig::default(),
+            jvm: JvmConfig::default(),
+            csharp: CSharpConfig::default(),
+            openapi: OpenApiConfig::default(),
+            targets: vec![],
+        }
+    }
+}
+
+/// Merges `overrides` into `base`, table by table.
+fn merge(base: &mut toml::Table, overrides: toml::Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(overrides)) => {
+                merge(base, overrides)
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
         }
     }
 }
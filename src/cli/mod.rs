@@ -24,4 +24,54 @@ pub enum Command {
         #[arg(value_name = "Dirs")]
         dirs: Vec<PathBuf>,
     },
+    /// Generate migrations from changes to `sql/`
+    Migrate {
+        #[command(subcommand)]
+        command: MigrateCommand,
+    },
+    /// Inspect the schema in `sql/`
+    Schema {
+        #[command(subcommand)]
+        command: SchemaCommand,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum SchemaCommand {
+    /// Print the tables, columns, keys and indexes quaff reads from `sql/`
+    Dump {
+        /// json or yaml
+        #[arg(long, default_value = "json")]
+        format: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum MigrateCommand {
+    /// Print the SQL that migrates a previous schema to the one in `sql/`
+    Diff {
+        /// Git ref holding the previous schema
+        #[arg(long, value_name = "REF", required_unless_present = "snapshot")]
+        from: Option<String>,
+
+        /// JSON snapshot holding the previous schema, written by `migrate snapshot`
+        #[arg(long, value_name = "FILE", conflicts_with = "from")]
+        snapshot: Option<PathBuf>,
+    },
+    /// Save the schema in `sql/` as a JSON snapshot to diff against later
+    Snapshot {
+        #[arg(value_name = "FILE", default_value = "schema.json")]
+        output: PathBuf,
+    },
+    /// Write a Laravel or Phinx migration creating each table in `sql/`
+    Export {
+        /// laravel or phinx
+        #[arg(long, default_value = "laravel")]
+        framework: String,
+
+        /// Directory to write to, `database/migrations` for Laravel and
+        /// `db/migrations` for Phinx by default
+        #[arg(long, value_name = "DIR")]
+        output: Option<PathBuf>,
+    },
 }
@@ -0,0 +1,378 @@
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::cli::config::{CSharpAttributes, CSharpStyle, Config, SchemaMode};
+use crate::lang::{Backend, CommentStyle, Model, PascalCase, SnakeCase, string_literal};
+use crate::sql::ir::{Column, SqlType};
+
+/// Generates a C# `record` or `class` per table, with nullable reference
+/// types enabled.
+pub struct CSharp {
+    schema_mode: SchemaMode,
+    namespace: String,
+    style: CSharpStyle,
+    attributes: CSharpAttributes,
+}
+
+/// A column as a property of the generated type.
+struct Property<'a> {
+    column: &'a Column,
+    name: String,
+    /// The type including the `?` of nullable columns.
+    type_name: String,
+    /// Attributes without brackets, e.g. `Key` or `Column("id")`.
+    attributes: Vec<String>,
+}
+
+impl CSharp {
+    pub fn new(config: &Config) -> anyhow::Result<Self> {
+        Ok(Self {
+            schema_mode: SchemaMode::from_str(&config.schema_mode)?,
+            namespace: config.csharp.namespace.clone(),
+            style: CSharpStyle::from_str(&config.csharp.style)?,
+            attributes: CSharpAttributes::from_str(&config.csharp.attributes)?,
+        })
+    }
+
+    /// Schema parts as sub-namespaces, e.g. `Billing`.
+    fn subnamespaces(&self, model: &Model) -> Vec<String> {
+        match self.schema_mode {
+            SchemaMode::Namespace => model
+                .table
+                .schema
+                .iter()
+                .map(|part| part.to_pascal_case())
+                .collect(),
+            SchemaMode::Prefix => vec![],
+        }
+    }
+
+    fn properties<'a>(&self, model: &'a Model) -> Vec<Property<'a>> {
+        let table = &model.table;
+        let primary_key = table.primary_key();
+
+        table
+            .columns
+            .iter()
+            .map(|column| {
+                let mut name = column.name.to_snake_case().to_pascal_case();
+                if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                    name = format!("Column{name}");
+                }
+                // Members can't share their enclosing type's name.
+                if name == model.class_name {
+                    name.push_str("Value");
+                }
+
+                let key = primary_key.contains(&column.name.as_str());
+                let mut attributes = vec![];
+                match self.attributes {
+                    CSharpAttributes::None => {}
+                    CSharpAttributes::Dapper => match (key, column.generated) {
+                        (true, true) => attributes.push(String::from("Key")),
+                        (true, false) => attributes.push(String::from("ExplicitKey")),
+                        (false, true) => attributes.push(String::from("Computed")),
+                        (false, false) => {}
+                    },
+                    CSharpAttributes::EfCore => {
+                        if key {
+                            attributes.push(String::from("Key"));
+                        }
+                        attributes.push(format!("Column({})", cs_string(&column.name)));
+                        if column.generated {
+                            attributes.push(String::from(match key {
+                                true => "DatabaseGenerated(DatabaseGeneratedOption.Identity)",
+                                false => "DatabaseGenerated(DatabaseGeneratedOption.Computed)",
+                            }));
+                        }
+                        if let (Some(max), SqlType::Char { .. } | SqlType::VarChar { .. }) =
+                            (column.sql_type.max_length(), &column.sql_type)
+                        {
+                            attributes.push(format!("MaxLength({})", max.value));
+                        }
+                    }
+                }
+
+                let mut type_name = type_of(&column.sql_type);
+                if column.nullable {
+                    type_name.push('?');
+                }
+
+                Property {
+                    column,
+                    name,
+                    type_name,
+                    attributes,
+                }
+            })
+            .collect()
+    }
+
+    fn type_attribute(&self, model: &Model) -> Option<String> {
+        let table = &model.table;
+        match self.attributes {
+            CSharpAttributes::None => None,
+            CSharpAttributes::Dapper => {
+                let name = table
+                    .schema
+                    .iter()
+                    .chain([&table.name])
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(".");
+                Some(format!("[Table({})]\n", cs_string(&name)))
+            }
+            CSharpAttributes::EfCore => match table.schema.is_empty() {
+                true => Some(format!("[Table({})]\n", cs_string(&table.name))),
+                false => Some(format!(
+                    "[Table({}, Schema = {})]\n",
+                    cs_string(&table.name),
+                    cs_string(&table.schema.join("."))
+                )),
+            },
+        }
+    }
+
+    fn record(&self, model: &Model) -> String {
+        let parameters: Vec<String> = self
+            .properties(model)
+            .into_iter()
+            .map(|property| {
+                // Attributes on positional parameters must target the
+                // generated property.
+                let attributes = match property.attributes.is_empty() {
+                    true => String::new(),
+                    false => format!("[property: {}] ", property.attributes.join(", ")),
+                };
+                format!("    {attributes}{} {}", property.type_name, property.name)
+            })
+            .collect();
+
+        format!(
+            "public record {}(\n{}\n);\n",
+            model.class_name,
+            parameters.join(",\n")
+        )
+    }
+
+    fn class(&self, model: &Model) -> String {
+        let properties: Vec<String> = self
+            .properties(model)
+            .into_iter()
+            .map(|property| {
+                let mut lines = match &property.column.comment {
+                    Some(comment) => DOC_COMMENT.render(comment.lines(), "    "),
+                    None => String::new(),
+                };
+                for attribute in &property.attributes {
+                    lines.push_str(&format!("    [{attribute}]\n"));
+                }
+
+                // Callers must supply every non-nullable column the database
+                // doesn't fill in.
+                let required = match property.column.nullable || property.column.generated {
+                    true => "",
+                    false => "required ",
+                };
+                lines.push_str(&format!(
+                    "    public {required}{} {} {{ get; set; }}\n",
+                    property.type_name, property.name
+                ));
+
+                lines
+            })
+            .collect();
+
+        format!(
+            "public class {}\n{{\n{}}}\n",
+            model.class_name,
+            properties.join("\n")
+        )
+    }
+}
+
+/// The C# type `sql_type` maps to, without nullability.
+fn type_of(sql_type: &SqlType) -> String {
+    let name = match sql_type {
+        SqlType::Integer { bytes, unsigned } => match (bytes, unsigned) {
+            (1, false) => "sbyte",
+            (1, true) => "byte",
+            (2, false) => "short",
+            (2, true) => "ushort",
+            (4, false) => "int",
+            (4, true) => "uint",
+            (8, false) => "long",
+            (8, true) => "ulong",
+            (16, false) => "Int128",
+            (16, true) => "UInt128",
+            _ => "decimal",
+        },
+        SqlType::Float { double: false } => "float",
+        SqlType::Float { double: true } => "double",
+        SqlType::Decimal { .. } => "decimal",
+        SqlType::Boolean => "bool",
+        SqlType::Char { .. }
+        | SqlType::VarChar { .. }
+        | SqlType::Text
+        | SqlType::Json
+        | SqlType::Enum { .. } => "string",
+        SqlType::Binary { .. } => "byte[]",
+        SqlType::Date => "DateOnly",
+        SqlType::Time => "TimeOnly",
+        SqlType::Timestamp { time_zone: true } => "DateTimeOffset",
+        SqlType::Timestamp { time_zone: false } => "DateTime",
+        SqlType::Interval => "TimeSpan",
+        SqlType::Uuid => "Guid",
+        SqlType::Other { .. } => "object",
+        SqlType::Array { element } => return format!("{}[]", type_of(element)),
+    };
+
+    name.to_string()
+}
+
+impl Backend for CSharp {
+    fn file_name(&self, model: &Model) -> PathBuf {
+        let mut path: PathBuf = self.subnamespaces(model).into_iter().collect();
+        path.push(format!("{}.cs", model.class_name));
+
+        path
+    }
+
+    fn preamble(&self, model: &Model) -> String {
+        let namespace = [self.namespace.clone()]
+            .into_iter()
+            .chain(self.subnamespaces(model))
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(".");
+
+        let mut usings = BTreeSet::new();
+        for column in &model.table.columns {
+            if matches!(
+                column.sql_type,
+                SqlType::Date
+                    | SqlType::Time
+                    | SqlType::Timestamp { .. }
+                    | SqlType::Interval
+                    | SqlType::Uuid
+            ) || matches!(column.sql_type, SqlType::Integer { bytes: 16, .. })
+            {
+                usings.insert("System");
+            }
+        }
+        match self.attributes {
+            CSharpAttributes::None => {}
+            CSharpAttributes::Dapper => {
+                usings.insert("Dapper.Contrib.Extensions");
+            }
+            CSharpAttributes::EfCore => {
+                usings.insert("System.ComponentModel.DataAnnotations");
+                usings.insert("System.ComponentModel.DataAnnotations.Schema");
+            }
+        }
+
+        // `System` namespaces go first, as `dotnet format` orders them.
+        let mut usings: Vec<&str> = usings.into_iter().collect();
+        usings.sort_by_key(|using| (!using.starts_with("System"), *using));
+        let usings: String = usings
+            .into_iter()
+            .map(|using| format!("using {using};\n"))
+            .collect();
+        let usings = match usings.is_empty() {
+            true => usings,
+            false => format!("{usings}\n"),
+        };
+
+        format!(
+            "// Generated by quaff from {}.sql.\n\n#nullable enable\n\n{usings}namespace {namespace};\n\n",
+            model.stem
+        )
+    }
+
+    fn type_name(&self, column: &Column) -> String {
+        type_of(&column.sql_type)
+    }
+
+    fn render(&self, model: &Model) -> anyhow::Result<String> {
+        let mut output = match &model.table.comment {
+            Some(comment) => DOC_COMMENT.render(comment.lines(), ""),
+            None => String::new(),
+        };
+        // Positional parameters are documented on the record itself.
+        if let CSharpStyle::Record = self.style {
+            for property in self.properties(model) {
+                if let Some(comment) = &property.column.comment {
+                    let comment: Vec<String> = comment.lines().map(xml_text).collect();
+                    output.push_str(&format!(
+                        "/// <param name=\"{}\">{}</param>\n",
+                        property.name,
+                        comment.join(" ")
+                    ));
+                }
+            }
+        }
+        if let Some(attribute) = self.type_attribute(model) {
+            output.push_str(&attribute);
+        }
+        output.push_str(&match self.style {
+            CSharpStyle::Record => self.record(model),
+            CSharpStyle::Class => self.class(model),
+        });
+
+        Ok(output)
+    }
+}
+
+/// XML `<summary>` doc comments.
+const DOC_COMMENT: CommentStyle = CommentStyle {
+    open: Some("/// <summary>"),
+    prefix: "///",
+    close: Some("/// </summary>"),
+    inline: false,
+    escape: xml_text,
+};
+
+/// Escapes `line` for an XML doc comment.
+fn xml_text(line: &str) -> String {
+    line.trim()
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Quotes `s` as a C# string literal.
+fn cs_string(s: &str) -> String {
+    string_literal(s, '"')
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lang::testing::render;
+
+    #[test]
+    fn members_dont_share_the_type_name() {
+        let code = render("lang = \"csharp\"", "CREATE TABLE status (status TEXT)");
+        assert!(code.contains("    string? StatusValue\n"));
+    }
+
+    #[test]
+    fn ef_core_attributes() {
+        let code = render(
+            "lang = \"csharp\"\n[csharp]\nstyle = \"class\"\nattributes = \"efcore\"",
+            "CREATE TABLE t (
+                id SERIAL PRIMARY KEY,
+                code VARCHAR(8) NOT NULL,
+                total INT GENERATED ALWAYS AS (id * 2) STORED
+            )",
+        );
+
+        assert!(code.contains(
+            "    [Key]\n    [Column(\"id\")]\n    [DatabaseGenerated(DatabaseGeneratedOption.Identity)]\n    public int Id { get; set; }\n"
+        ));
+        assert!(code.contains(
+            "    [Column(\"code\")]\n    [MaxLength(8)]\n    public required string Code { get; set; }\n"
+        ));
+        assert!(code.contains("    [DatabaseGenerated(DatabaseGeneratedOption.Computed)]\n"));
+    }
+}
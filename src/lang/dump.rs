@@ -0,0 +1,261 @@
+use anyhow::anyhow;
+use serde::Serialize;
+use serde_json::Value;
+use std::str::FromStr;
+
+use super::{Backend, Model};
+use crate::sql::ir::{self, Constraint, ForeignKey, Index};
+
+/// The output format of `quaff schema dump`.
+#[derive(Debug, Clone, Copy)]
+pub enum DumpFormat {
+    Json,
+    Yaml,
+}
+
+impl FromStr for DumpFormat {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "yaml" | "yml" => Ok(Self::Yaml),
+            _ => Err(anyhow!("unknown dump format: {s}")),
+        }
+    }
+}
+
+/// The tables in `sql/` as quaff understands them, for other tools to
+/// consume.
+#[derive(Debug, Serialize)]
+pub struct Dump<'a> {
+    tables: Vec<Table<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct Table<'a> {
+    name: &'a str,
+    schema: &'a [String],
+    class_name: &'a str,
+    comment: Option<&'a str>,
+    columns: Vec<Column<'a>>,
+    primary_key: Vec<&'a str>,
+    foreign_keys: Vec<&'a ForeignKey>,
+    /// Unique constraints, then plain indexes.
+    indexes: Vec<Index>,
+    checks: Vec<Check<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct Column<'a> {
+    name: &'a str,
+    sql_type: String,
+    /// The type the column maps to in the configured language.
+    kind: String,
+    nullable: bool,
+    default: Option<String>,
+    generated: bool,
+    primary_key: bool,
+    unique: bool,
+    max_length: Option<u64>,
+    unsigned: bool,
+    comment: Option<&'a str>,
+}
+
+#[derive(Debug, Serialize)]
+struct Check<'a> {
+    name: Option<&'a str>,
+    sql: String,
+}
+
+impl<'a> Dump<'a> {
+    /// Dumps each model's table, with types mapped by `backend`.
+    pub fn new(models: &'a [Model], backend: &dyn Backend) -> Self {
+        Self {
+            tables: models
+                .iter()
+                .map(|model| Table::new(model, backend))
+                .collect(),
+        }
+    }
+
+    pub fn render(&self, format: DumpFormat) -> anyhow::Result<String> {
+        match format {
+            DumpFormat::Json => Ok(serde_json::to_string_pretty(self)?),
+            DumpFormat::Yaml => Ok(yaml(&serde_json::to_value(self)?).join("\n") + "\n"),
+        }
+    }
+}
+
+impl<'a> Table<'a> {
+    fn new(model: &'a Model, backend: &dyn Backend) -> Self {
+        let table = &model.table;
+        let unique = table
+            .constraints
+            .iter()
+            .filter_map(|constraint| match constraint {
+                Constraint::Unique { name, columns } => Some(Index {
+                    name: name.clone(),
+                    columns: columns.clone(),
+                    unique: true,
+                }),
+                _ => None,
+            });
+
+        Self {
+            name: &table.name,
+            schema: &table.schema,
+            class_name: &model.class_name,
+            comment: table.comment.as_deref(),
+            columns: table
+                .columns
+                .iter()
+                .map(|column| Column::new(table, column, backend))
+                .collect(),
+            primary_key: table.primary_key(),
+            foreign_keys: table.foreign_keys().collect(),
+            indexes: unique.chain(table.indexes.iter().cloned()).collect(),
+            checks: table
+                .checks()
+                .map(|(name, expr)| Check {
+                    name,
+                    sql: expr.to_string(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<'a> Column<'a> {
+    fn new(table: &ir::Table, column: &'a ir::Column, backend: &dyn Backend) -> Self {
+        Self {
+            name: &column.name,
+            sql_type: column.data_type.to_string(),
+            kind: backend.type_name(column),
+            nullable: column.nullable,
+            default: column.default.as_ref().map(|expr| expr.to_string()),
+            generated: column.generated,
+            primary_key: table.primary_key().contains(&column.name.as_str()),
+            unique: table.is_unique(&column.name),
+            max_length: column.sql_type.max_length().map(|max| max.value),
+            unsigned: column.sql_type.is_unsigned(),
+            comment: column.comment.as_deref(),
+        }
+    }
+}
+
+/// The lines of `value` as block-style YAML, with nested lists at their key's
+/// indentation like most YAML emitters.
+fn yaml(value: &Value) -> Vec<String> {
+    let mut lines = vec![];
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                let key = yaml_string(key);
+                match yaml_scalar(value) {
+                    Some(scalar) => lines.push(format!("{key}: {scalar}")),
+                    None => {
+                        lines.push(format!("{key}:"));
+                        let indent = if value.is_array() { "" } else { "  " };
+                        lines.extend(
+                            yaml(value)
+                                .into_iter()
+                                .map(|line| indent.to_string() + &line),
+                        );
+                    }
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                match yaml_scalar(item) {
+                    Some(scalar) => lines.push(format!("- {scalar}")),
+                    None => {
+                        for (i, line) in yaml(item).into_iter().enumerate() {
+                            let prefix = if i == 0 { "- " } else { "  " };
+                            lines.push(prefix.to_string() + &line);
+                        }
+                    }
+                }
+            }
+        }
+        _ => lines.extend(yaml_scalar(value)),
+    }
+    lines
+}
+
+/// `value` on one line, or `None` for a non-empty map or list.
+fn yaml_scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => Some("null".to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::String(s) => Some(yaml_string(s)),
+        Value::Array(items) if items.is_empty() => Some("[]".to_string()),
+        Value::Object(map) if map.is_empty() => Some("{}".to_string()),
+        _ => None,
+    }
+}
+
+/// `s` unquoted when YAML would read it back as the same string, otherwise
+/// double-quoted. JSON's escapes are valid in YAML's double-quoted strings.
+fn yaml_string(s: &str) -> String {
+    let plain = !s.is_empty()
+        && s.trim() == s
+        && !s.starts_with(|c: char| c.is_ascii_digit() || "-+.?:,[]{}#&*!|>'\"%@`~".contains(c))
+        && !s.ends_with(':')
+        && !s.contains(": ")
+        && !s.contains(" #")
+        && !s.contains(char::is_control)
+        && !matches!(
+            s.to_lowercase().as_str(),
+            "null" | "true" | "false" | "yes" | "no" | "on" | "off" | "y" | "n"
+        )
+        && s.parse::<f64>().is_err();
+    if plain {
+        s.to_string()
+    } else {
+        serde_json::to_string(s).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn yaml_blocks() {
+        let value = json!({
+            "tables": [{ "name": "users", "schema": [], "columns": [{ "name": "id" }] }],
+            "meta": { "count": 1, "empty": {} },
+        });
+        assert_eq!(
+            yaml(&value),
+            [
+                "tables:",
+                "- name: users",
+                "  schema: []",
+                "  columns:",
+                "  - name: id",
+                "meta:",
+                "  count: 1",
+                "  empty: {}",
+            ]
+        );
+    }
+
+    #[test]
+    fn yaml_strings() {
+        assert_eq!(yaml_string("ENUM('a', 'b''c')"), "ENUM('a', 'b''c')");
+        assert_eq!(
+            yaml_string("Login e-mail, never */ shared"),
+            "Login e-mail, never */ shared"
+        );
+        for quoted in [
+            "", "null", "No", "6", "1.5", "inf", "-x", " x", "a: b", "a #b", "'a'",
+        ] {
+            assert_eq!(yaml_string(quoted), serde_json::to_string(quoted).unwrap());
+        }
+        assert_eq!(yaml_string("Multi\nline"), r#""Multi\nline""#);
+    }
+}
@@ -0,0 +1,241 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::cli::config::{Config, NullStyle, SchemaMode};
+use crate::lang::{Backend, CommentStyle, Model, PascalCase, SnakeCase};
+use crate::sql::ir::{Column, SqlType};
+
+/// Words golint wants in all caps inside identifiers.
+const INITIALISMS: &[&str] = &[
+    "api", "html", "http", "https", "id", "ip", "json", "sql", "ssh", "uri", "url", "uuid", "xml",
+];
+
+/// Generates one struct per table with `db` and `json` tags.
+pub struct Go {
+    schema_mode: SchemaMode,
+    package: String,
+    nulls: NullStyle,
+}
+
+impl Go {
+    pub fn new(config: &Config) -> anyhow::Result<Self> {
+        Ok(Self {
+            schema_mode: SchemaMode::from_str(&config.schema_mode)?,
+            package: config.go.package.clone(),
+            nulls: NullStyle::from_str(&config.go.nulls)?,
+        })
+    }
+
+    /// The directories `model` is generated in. Each is a package of its own.
+    fn packages(&self, model: &Model) -> Vec<String> {
+        match self.schema_mode {
+            SchemaMode::Namespace => model
+                .table
+                .schema
+                .iter()
+                .map(|part| part.to_snake_case().replace('_', ""))
+                .collect(),
+            SchemaMode::Prefix => vec![],
+        }
+    }
+
+    fn field_type(&self, column: &Column) -> String {
+        let type_name = type_of(&column.sql_type);
+        // Slices already have `nil`.
+        if !column.nullable || type_name.starts_with("[]") || type_name == "json.RawMessage" {
+            return type_name;
+        }
+
+        match self.nulls {
+            NullStyle::Pointer => format!("*{type_name}"),
+            NullStyle::Sql => match type_name.as_str() {
+                "string" => String::from("sql.NullString"),
+                "int64" => String::from("sql.NullInt64"),
+                "int32" => String::from("sql.NullInt32"),
+                "int16" => String::from("sql.NullInt16"),
+                "uint8" => String::from("sql.NullByte"),
+                "float64" => String::from("sql.NullFloat64"),
+                "bool" => String::from("sql.NullBool"),
+                "time.Time" => String::from("sql.NullTime"),
+                _ => format!("sql.Null[{type_name}]"),
+            },
+        }
+    }
+}
+
+fn type_of(sql_type: &SqlType) -> String {
+    let name = match sql_type {
+        SqlType::Integer { bytes, unsigned } => match (bytes, unsigned) {
+            (1, false) => "int8",
+            (1, true) => "uint8",
+            (2, false) => "int16",
+            (2, true) => "uint16",
+            (4, false) => "int32",
+            (4, true) => "uint32",
+            (8, false) => "int64",
+            (8, true) => "uint64",
+            // Wider than any Go integer.
+            _ => "string",
+        },
+        SqlType::Float { double: true } => "float64",
+        SqlType::Float { double: false } => "float32",
+        SqlType::Boolean => "bool",
+        SqlType::Decimal { .. }
+        | SqlType::Char { .. }
+        | SqlType::VarChar { .. }
+        | SqlType::Text
+        | SqlType::Interval
+        | SqlType::Uuid
+        | SqlType::Enum { .. }
+        | SqlType::Other { .. } => "string",
+        SqlType::Binary { .. } => "[]byte",
+        SqlType::Date | SqlType::Time | SqlType::Timestamp { .. } => "time.Time",
+        SqlType::Json => "json.RawMessage",
+        SqlType::Array { element } => return format!("[]{}", type_of(element)),
+    };
+
+    name.to_string()
+}
+
+impl Backend for Go {
+    fn file_name(&self, model: &Model) -> PathBuf {
+        let mut path: PathBuf = self.packages(model).into_iter().collect();
+        path.push(format!("{}.go", model.stem));
+
+        path
+    }
+
+    fn preamble(&self, model: &Model) -> String {
+        let package = self.packages(model).pop().unwrap_or(self.package.clone());
+        let types: Vec<String> = model
+            .table
+            .columns
+            .iter()
+            .map(|column| self.field_type(column))
+            .collect();
+
+        let imports: Vec<&str> = [
+            ("sql.", "database/sql"),
+            ("json.", "encoding/json"),
+            ("time.", "time"),
+        ]
+        .into_iter()
+        .filter(|(prefix, _)| types.iter().any(|t| t.contains(prefix)))
+        .map(|(_, path)| path)
+        .collect();
+
+        let imports = match imports.as_slice() {
+            [] => String::new(),
+            [path] => format!("import \"{path}\"\n\n"),
+            paths => format!(
+                "import (\n{})\n\n",
+                paths
+                    .iter()
+                    .map(|path| format!("\t\"{path}\"\n"))
+                    .collect::<String>()
+            ),
+        };
+
+        format!(
+            "// Code generated by quaff from {}.sql. DO NOT EDIT.\n\npackage {package}\n\n{imports}",
+            model.stem
+        )
+    }
+
+    fn type_name(&self, column: &Column) -> String {
+        type_of(&column.sql_type)
+    }
+
+    fn render(&self, model: &Model) -> anyhow::Result<String> {
+        let fields: Vec<(String, String, &Column)> = model
+            .table
+            .columns
+            .iter()
+            .map(|column| (field_name(&column.name), self.field_type(column), column))
+            .collect();
+
+        // gofmt aligns the types and tags of consecutive fields. A comment
+        // line ends the run.
+        let mut runs: Vec<&[(String, String, &Column)]> = vec![];
+        let mut start = 0;
+        for i in 1..=fields.len() {
+            if i == fields.len() || fields[i].2.comment.is_some() {
+                runs.push(&fields[start..i]);
+                start = i;
+            }
+        }
+
+        let mut body = String::new();
+        for run in runs {
+            let name_width = run.iter().map(|(name, ..)| name.len()).max().unwrap_or(0);
+            let type_width = run.iter().map(|(_, t, _)| t.len()).max().unwrap_or(0);
+
+            for (name, type_name, column) in run {
+                if let Some(comment) = &column.comment {
+                    body.push_str(&CommentStyle::lines("//").render(comment.lines(), "\t"));
+                }
+                body.push_str(&format!(
+                    "\t{name:name_width$} {type_name:type_width$} `db:\"{0}\" json:\"{0}\"`\n",
+                    column.name
+                ));
+            }
+        }
+
+        let doc = match &model.table.comment {
+            Some(comment) => CommentStyle::lines("//").render(comment.lines(), ""),
+            None => String::new(),
+        };
+
+        Ok(format!(
+            "{doc}type {} struct {{\n{body}}}\n",
+            model.class_name
+        ))
+    }
+}
+
+/// Exported Go name for `column`, e.g. `UserID` for `user_id`.
+fn field_name(column: &str) -> String {
+    let name: String = column
+        .to_snake_case()
+        .split('_')
+        .map(|word| match INITIALISMS.contains(&word) {
+            true => word.to_uppercase(),
+            false => word.to_pascal_case(),
+        })
+        .collect();
+
+    match name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        true => name,
+        false => format!("Column{name}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lang::testing::render;
+
+    #[test]
+    fn field_names() {
+        assert_eq!(field_name("user_id"), "UserID");
+        assert_eq!(field_name("api_url"), "APIURL");
+        assert_eq!(field_name("createdAt"), "CreatedAt");
+        assert_eq!(field_name("2fa_code"), "Column2faCode");
+    }
+
+    #[test]
+    fn nullable_fields() {
+        let sql = "CREATE TABLE t (a INT, b TEXT[], c JSON, d SMALLINT UNSIGNED)";
+
+        let code = render("lang = \"go\"", sql);
+        assert!(code.contains("\tA *int32 "));
+        assert!(code.contains("\tB []string "));
+        assert!(code.contains("\tC json.RawMessage "));
+        assert!(code.contains("\tD *uint16 "));
+
+        let code = render("lang = \"go\"\n[go]\nnulls = \"sql\"", sql);
+        assert!(code.contains("\tA sql.NullInt32 "));
+        assert!(code.contains("\tB []string "));
+        assert!(code.contains("\tD sql.Null[uint16] "));
+    }
+}
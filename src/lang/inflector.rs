@@ -0,0 +1,168 @@
+/// Words whose singular form can't be derived from a suffix rule.
+const IRREGULARS: &[(&str, &str)] = &[
+    ("people", "person"),
+    ("men", "man"),
+    ("women", "woman"),
+    ("children", "child"),
+    ("teeth", "tooth"),
+    ("feet", "foot"),
+    ("geese", "goose"),
+    ("mice", "mouse"),
+    ("oxen", "ox"),
+    ("data", "datum"),
+    ("criteria", "criterion"),
+    ("indices", "index"),
+    ("matrices", "matrix"),
+    ("vertices", "vertex"),
+    ("analyses", "analysis"),
+    ("theses", "thesis"),
+    ("crises", "crisis"),
+    ("quizzes", "quiz"),
+    ("statuses", "status"),
+    ("aliases", "alias"),
+    ("buses", "bus"),
+    ("abuses", "abuse"),
+    ("excuses", "excuse"),
+    ("fuses", "fuse"),
+    ("movies", "movie"),
+    ("cookies", "cookie"),
+    ("leaves", "leaf"),
+    ("lives", "life"),
+    ("wives", "wife"),
+    ("knives", "knife"),
+    ("halves", "half"),
+    ("shelves", "shelf"),
+    ("heroes", "hero"),
+    ("potatoes", "potato"),
+    ("tomatoes", "tomato"),
+    ("echoes", "echo"),
+    ("vetoes", "veto"),
+    ("caches", "cache"),
+    ("niches", "niche"),
+    ("headaches", "headache"),
+    ("avalanches", "avalanche"),
+    ("moustaches", "moustache"),
+];
+
+/// Words that are spelled the same in singular and plural.
+const UNCOUNTABLES: &[&str] = &[
+    "equipment",
+    "information",
+    "rice",
+    "money",
+    "species",
+    "series",
+    "fish",
+    "sheep",
+    "deer",
+    "news",
+    "metadata",
+    "status",
+    "access",
+    "analytics",
+];
+
+/// Suffix rules, checked in order. The first matching suffix is replaced.
+const RULES: &[(&str, &str)] = &[
+    ("ies", "y"),
+    ("sses", "ss"),
+    ("shes", "sh"),
+    ("ches", "ch"),
+    ("xes", "x"),
+    ("zzes", "zz"),
+    ("ouses", "ouse"),
+    ("auses", "ause"),
+    ("uses", "us"),
+    ("ses", "se"),
+    ("ss", "ss"),
+    ("us", "us"),
+    ("is", "is"),
+    ("s", ""),
+];
+
+/// Converts an English plural word into its singular form. Only the last
+/// segment of a `snake_case` or `kebab-case` identifier is inflected, so
+/// `order_items` becomes `order_item`.
+pub fn singularize(word: &str) -> String {
+    let split = word
+        .rfind(|c: char| !c.is_alphanumeric())
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    let (head, last) = word.split_at(split);
+
+    format!("{head}{}", singularize_word(last))
+}
+
+fn singularize_word(word: &str) -> String {
+    let lower = word.to_lowercase();
+
+    if UNCOUNTABLES.contains(&lower.as_str()) {
+        return word.to_string();
+    }
+
+    if let Some((_, singular)) = IRREGULARS.iter().find(|(plural, _)| *plural == lower) {
+        return singular.to_string();
+    }
+
+    for (suffix, replacement) in RULES {
+        if lower.len() > suffix.len() && lower.ends_with(suffix) {
+            return format!("{}{replacement}", &word[..word.len() - suffix.len()]);
+        }
+    }
+
+    word.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::singularize;
+
+    #[test]
+    fn suffix_rules() {
+        for (plural, singular) in [
+            ("users", "user"),
+            ("categories", "category"),
+            ("addresses", "address"),
+            ("wishes", "wish"),
+            ("matches", "match"),
+            ("boxes", "box"),
+            ("buzzes", "buzz"),
+            ("sizes", "size"),
+            ("prizes", "prize"),
+            ("shoes", "shoe"),
+            ("responses", "response"),
+            ("bonuses", "bonus"),
+            ("viruses", "virus"),
+            ("campuses", "campus"),
+            ("warehouses", "warehouse"),
+            ("causes", "cause"),
+            ("settings", "setting"),
+        ] {
+            assert_eq!(singularize(plural), singular, "{plural}");
+        }
+    }
+
+    #[test]
+    fn irregulars_and_uncountables() {
+        for (plural, singular) in [
+            ("people", "person"),
+            ("quizzes", "quiz"),
+            ("heroes", "hero"),
+            ("caches", "cache"),
+            ("excuses", "excuse"),
+            ("statuses", "status"),
+            ("news", "news"),
+            ("status", "status"),
+            ("address", "address"),
+        ] {
+            assert_eq!(singularize(plural), singular, "{plural}");
+        }
+    }
+
+    #[test]
+    fn only_the_last_segment() {
+        assert_eq!(singularize("order_items"), "order_item");
+        assert_eq!(singularize("user-addresses"), "user-address");
+        assert_eq!(singularize("Categories"), "Category");
+    }
+}
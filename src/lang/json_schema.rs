@@ -0,0 +1,340 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use serde_json::{Map, Value, json};
+use sqlparser::ast::{self, Expr, UnaryOperator};
+
+use crate::cli::config::{Config, SchemaMode};
+use crate::lang::{Backend, File, Model, PascalCase, SnakeCase};
+use crate::sql::ir::{Column, SqlType};
+use crate::sql::query::NamedQuery;
+
+const DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+const OPENAPI_VERSION: &str = "3.1.0";
+/// Exact numerics as strings, e.g. `-12.50`.
+const DECIMAL_PATTERN: &str = r"^-?\d+(\.\d+)?$";
+
+pub enum Output {
+    /// A JSON Schema document per table.
+    Document,
+    /// One OpenAPI document with every table under `components.schemas`.
+    OpenApi,
+}
+
+/// Describes the rows of each table as a JSON Schema, for API docs and
+/// validators.
+pub struct JsonSchema {
+    schema_mode: SchemaMode,
+    output: Output,
+    title: String,
+    version: String,
+}
+
+impl JsonSchema {
+    pub fn new(config: &Config, output: Output) -> anyhow::Result<Self> {
+        Ok(Self {
+            schema_mode: SchemaMode::from_str(&config.schema_mode)?,
+            output,
+            title: config.openapi.title.clone(),
+            version: config.openapi.version.clone(),
+        })
+    }
+
+    fn dirs(&self, model: &Model) -> Vec<String> {
+        match self.schema_mode {
+            SchemaMode::Namespace => model
+                .table
+                .schema
+                .iter()
+                .map(|part| part.to_snake_case())
+                .collect(),
+            SchemaMode::Prefix => vec![],
+        }
+    }
+
+    /// The key of `model` in `components.schemas`, e.g. `Billing.Invoice`.
+    fn component_name(&self, model: &Model) -> String {
+        self.dirs(model)
+            .iter()
+            .map(|part| part.to_pascal_case())
+            .chain([model.class_name.clone()])
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    /// The schema of one row of `model`.
+    fn schema(&self, model: &Model) -> Map<String, Value> {
+        let mut properties = Map::new();
+        let mut required = vec![];
+        for column in &model.table.columns {
+            properties.insert(column.name.clone(), Value::Object(property(column)));
+            if !column.nullable {
+                required.push(Value::String(column.name.clone()));
+            }
+        }
+
+        let mut schema = Map::new();
+        schema.insert("title".into(), model.class_name.clone().into());
+        if let Some(comment) = &model.table.comment {
+            schema.insert("description".into(), comment.trim().into());
+        }
+        schema.insert("type".into(), "object".into());
+        schema.insert("properties".into(), Value::Object(properties));
+        if !required.is_empty() {
+            schema.insert("required".into(), Value::Array(required));
+        }
+        schema.insert("additionalProperties".into(), false.into());
+
+        schema
+    }
+}
+
+/// The schema of `column`'s values.
+fn property(column: &Column) -> Map<String, Value> {
+    let Value::Object(mut property) = type_of(&column.sql_type) else {
+        unreachable!("type_of always returns an object");
+    };
+
+    if column.nullable {
+        if let Some(Value::String(name)) = property.get("type") {
+            property["type"] = json!([name, "null"]);
+        }
+        // `enum` is checked on its own, so it has to list `null` as well.
+        if let Some(Value::Array(values)) = property.get_mut("enum") {
+            values.push(Value::Null);
+        }
+    }
+    if let Some(comment) = &column.comment {
+        property.insert("description".into(), comment.trim().into());
+    }
+    if column.generated {
+        property.insert("readOnly".into(), true.into());
+    }
+    if let Some(default) = column
+        .default
+        .as_ref()
+        .and_then(|expr| default_of(expr, &column.sql_type))
+    {
+        property.insert("default".into(), default);
+    }
+
+    property
+}
+
+/// The schema `sql_type` maps to. Always an object.
+fn type_of(sql_type: &SqlType) -> Value {
+    match sql_type {
+        SqlType::Integer { bytes, unsigned } => {
+            let mut schema = json!({ "type": "integer" });
+            match (bytes, unsigned) {
+                (1 | 2 | 4, false) | (1 | 2, true) => schema["format"] = "int32".into(),
+                (4, true) | (8, false) => schema["format"] = "int64".into(),
+                // Beyond what OpenAPI has a format for.
+                _ => {}
+            }
+            if *unsigned {
+                schema["minimum"] = 0.into();
+            }
+            schema
+        }
+        SqlType::Float { double: false } => json!({ "type": "number", "format": "float" }),
+        SqlType::Float { double: true } => json!({ "type": "number", "format": "double" }),
+        // Sent as strings so no precision is lost.
+        SqlType::Decimal { .. } => json!({ "type": "string", "pattern": DECIMAL_PATTERN }),
+        SqlType::Boolean => json!({ "type": "boolean" }),
+        SqlType::Char { length } | SqlType::VarChar { length } => match length {
+            Some(length) => json!({ "type": "string", "maxLength": length.value }),
+            None => json!({ "type": "string" }),
+        },
+        SqlType::Text => json!({ "type": "string" }),
+        SqlType::Binary { .. } => json!({ "type": "string", "contentEncoding": "base64" }),
+        SqlType::Date => json!({ "type": "string", "format": "date" }),
+        SqlType::Time => json!({ "type": "string", "format": "time" }),
+        SqlType::Timestamp { .. } => json!({ "type": "string", "format": "date-time" }),
+        SqlType::Interval => json!({ "type": "string", "format": "duration" }),
+        SqlType::Uuid => json!({ "type": "string", "format": "uuid" }),
+        SqlType::Array { element } => json!({ "type": "array", "items": type_of(element) }),
+        SqlType::Enum { values } => json!({ "type": "string", "enum": values }),
+        // Any JSON value.
+        SqlType::Json | SqlType::Other { .. } => json!({}),
+    }
+}
+
+/// The JSON value of a literal column default, if it is one.
+fn default_of(expr: &Expr, sql_type: &SqlType) -> Option<Value> {
+    let (value, sign) = match expr {
+        Expr::Value(v) => (&v.value, ""),
+        Expr::UnaryOp {
+            op: UnaryOperator::Minus,
+            expr,
+        } => match expr.as_ref() {
+            Expr::Value(v) => (&v.value, "-"),
+            _ => return None,
+        },
+        Expr::Nested(expr) => return default_of(expr, sql_type),
+        _ => return None,
+    };
+
+    match (value, sql_type) {
+        (ast::Value::Boolean(b), SqlType::Boolean) => Some((*b).into()),
+        (ast::Value::Number(n, _), SqlType::Boolean) => match n.as_str() {
+            "0" => Some(false.into()),
+            "1" => Some(true.into()),
+            _ => None,
+        },
+        (ast::Value::Number(n, _), SqlType::Integer { .. }) => {
+            format!("{sign}{n}").parse::<i64>().ok().map(Into::into)
+        }
+        (ast::Value::Number(n, _), SqlType::Float { .. }) => {
+            format!("{sign}{n}").parse::<f64>().ok().map(Into::into)
+        }
+        (ast::Value::Number(n, _), SqlType::Decimal { .. }) => Some(format!("{sign}{n}").into()),
+        (
+            ast::Value::SingleQuotedString(s)
+            | ast::Value::DoubleQuotedString(s)
+            | ast::Value::NationalStringLiteral(s)
+            | ast::Value::EscapedStringLiteral(s),
+            SqlType::Char { .. }
+            | SqlType::VarChar { .. }
+            | SqlType::Text
+            | SqlType::Enum { .. }
+            | SqlType::Decimal { .. }
+            | SqlType::Date
+            | SqlType::Time
+            | SqlType::Timestamp { .. }
+            | SqlType::Uuid,
+        ) => Some(s.clone().into()),
+        _ => None,
+    }
+}
+
+impl Backend for JsonSchema {
+    fn file_name(&self, model: &Model) -> PathBuf {
+        let mut path: PathBuf = self.dirs(model).into_iter().collect();
+        path.push(format!("{}.schema.json", model.stem));
+
+        path
+    }
+
+    /// JSON has no comments, so the header is a `$comment` in the document
+    /// instead.
+    fn preamble(&self, _model: &Model) -> String {
+        String::new()
+    }
+
+    fn type_name(&self, column: &Column) -> String {
+        match type_of(&column.sql_type).get("type") {
+            Some(Value::String(name)) => name.clone(),
+            _ => String::from("any"),
+        }
+    }
+
+    fn render(&self, model: &Model) -> anyhow::Result<String> {
+        let mut document = Map::new();
+        document.insert("$schema".into(), DIALECT.into());
+        document.insert(
+            "$comment".into(),
+            format!("Generated by quaff from {}.sql.", model.stem).into(),
+        );
+        document.extend(self.schema(model));
+
+        Ok(serde_json::to_string_pretty(&document)? + "\n")
+    }
+
+    fn model_file(&self, model: &Model) -> anyhow::Result<Option<File>> {
+        match self.output {
+            Output::Document => Ok(Some(File {
+                path: self.file_name(model),
+                contents: self.render(model)?,
+            })),
+            Output::OpenApi => Ok(None),
+        }
+    }
+
+    /// In OpenAPI mode, the `openapi.json` holding every model.
+    fn extra_files(
+        &self,
+        models: &[Model],
+        _queries: &[(String, Vec<NamedQuery>)],
+    ) -> anyhow::Result<Vec<File>> {
+        let Output::OpenApi = self.output else {
+            return Ok(vec![]);
+        };
+
+        let mut schemas = Map::new();
+        let mut tables: HashMap<String, String> = HashMap::new();
+        for model in models {
+            let name = self.component_name(model);
+            let table = model.table.qualified_name().join(".");
+            if let Some(other) = tables.insert(name.clone(), table.clone()) {
+                return Err(anyhow!(
+                    "tables {other} and {table} are both named {name} in components.schemas; \
+                     rename one in class_names"
+                ));
+            }
+            schemas.insert(name, Value::Object(self.schema(model)));
+        }
+        let document = json!({
+            "openapi": OPENAPI_VERSION,
+            "info": {
+                "title": self.title,
+                "version": self.version,
+            },
+            "components": {
+                "schemas": schemas,
+            },
+        });
+
+        Ok(vec![File {
+            path: PathBuf::from("openapi.json"),
+            contents: serde_json::to_string_pretty(&document)? + "\n",
+        }])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lang::testing::table;
+
+    fn model(sql: &str, class_name: &str) -> Model {
+        Model {
+            table: table(sql),
+            stem: class_name.to_lowercase(),
+            class_name: class_name.to_string(),
+        }
+    }
+
+    #[test]
+    fn component_names_must_be_unique() {
+        let config = Config {
+            schema_mode: "prefix".to_string(),
+            ..Config::default()
+        };
+        let openapi = JsonSchema::new(&config, Output::OpenApi).unwrap();
+        let models = [
+            model("CREATE TABLE billing.invoices (id INT);", "Invoice"),
+            model("CREATE TABLE shop.invoices (id INT);", "Invoice"),
+        ];
+        let err = openapi.extra_files(&models, &[]).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "tables billing.invoices and shop.invoices are both named Invoice in \
+             components.schemas; rename one in class_names"
+        );
+    }
+
+    #[test]
+    fn decimals_are_patterned_strings() {
+        let decimal = type_of(&SqlType::Decimal {
+            precision: Some(10),
+            scale: Some(2),
+        });
+        let pattern = decimal["pattern"].as_str().unwrap();
+        assert_eq!(decimal["type"], "string");
+        assert!(decimal.get("format").is_none());
+        assert_eq!(pattern, r"^-?\d+(\.\d+)?$");
+    }
+}
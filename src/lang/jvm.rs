@@ -0,0 +1,517 @@
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::cli::config::{Config, SchemaMode};
+use crate::lang::{Backend, CamelCase, CommentStyle, Model, PascalCase, SnakeCase, string_literal};
+use crate::sql::ir::{Column, SqlType};
+
+const KOTLIN_KEYWORDS: &[&str] = &[
+    "as",
+    "break",
+    "class",
+    "continue",
+    "do",
+    "else",
+    "false",
+    "for",
+    "fun",
+    "if",
+    "in",
+    "interface",
+    "is",
+    "null",
+    "object",
+    "package",
+    "return",
+    "super",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typealias",
+    "typeof",
+    "val",
+    "var",
+    "when",
+    "while",
+];
+
+const JAVA_KEYWORDS: &[&str] = &[
+    "abstract",
+    "assert",
+    "boolean",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extends",
+    "false",
+    "final",
+    "finally",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "implements",
+    "import",
+    "instanceof",
+    "int",
+    "interface",
+    "long",
+    "native",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "record",
+    "return",
+    "short",
+    "static",
+    "strictfp",
+    "super",
+    "switch",
+    "synchronized",
+    "this",
+    "throw",
+    "throws",
+    "transient",
+    "true",
+    "try",
+    "var",
+    "void",
+    "volatile",
+    "while",
+    "yield",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Language {
+    Kotlin,
+    Java,
+}
+
+/// Generates a Kotlin `data class` or Java `record` per table. With JPA
+/// enabled, Java gets a mutable entity class instead, since records can't be
+/// entities.
+pub struct Jvm {
+    language: Language,
+    schema_mode: SchemaMode,
+    package: String,
+    jpa: bool,
+}
+
+/// A column as a property of the generated class.
+struct Property<'a> {
+    column: &'a Column,
+    name: String,
+    /// The type without nullability, e.g. `Long` or `long`.
+    type_name: String,
+    /// Whether the property accepts `null`. Generated columns do until the
+    /// row is saved.
+    nullable: bool,
+    annotations: Vec<String>,
+}
+
+impl Jvm {
+    pub fn new(config: &Config, language: Language) -> anyhow::Result<Self> {
+        Ok(Self {
+            language,
+            schema_mode: SchemaMode::from_str(&config.schema_mode)?,
+            package: config.jvm.package.clone(),
+            jpa: config.jvm.jpa,
+        })
+    }
+
+    /// Sub-packages for the table's schema, e.g. `billing`.
+    fn subpackages(&self, model: &Model) -> Vec<String> {
+        match self.schema_mode {
+            SchemaMode::Namespace => model
+                .table
+                .schema
+                .iter()
+                .map(|part| part.to_snake_case().replace('_', ""))
+                .collect(),
+            SchemaMode::Prefix => vec![],
+        }
+    }
+
+    /// The type `sql_type` maps to. Java primitives are used unless `boxed`.
+    fn type_of(&self, sql_type: &SqlType, boxed: bool) -> String {
+        let kotlin = self.language == Language::Kotlin;
+        let primitive =
+            |kotlin_name: &str, java_primitive: &str, java_boxed: &str| match (kotlin, boxed) {
+                (true, _) => kotlin_name.to_string(),
+                (false, false) => java_primitive.to_string(),
+                (false, true) => java_boxed.to_string(),
+            };
+
+        match sql_type {
+            // Unsigned columns widen to the next type so every value fits.
+            SqlType::Integer { bytes, unsigned } => match (bytes, unsigned) {
+                (1, false) => primitive("Byte", "byte", "Byte"),
+                (1, true) | (2, false) => primitive("Short", "short", "Short"),
+                (2, true) | (4, false) => primitive("Int", "int", "Integer"),
+                (4, true) | (8, false) => primitive("Long", "long", "Long"),
+                _ => String::from("BigInteger"),
+            },
+            SqlType::Float { double: false } => primitive("Float", "float", "Float"),
+            SqlType::Float { double: true } => primitive("Double", "double", "Double"),
+            SqlType::Boolean => primitive("Boolean", "boolean", "Boolean"),
+            SqlType::Decimal { .. } => String::from("BigDecimal"),
+            SqlType::Char { .. }
+            | SqlType::VarChar { .. }
+            | SqlType::Text
+            | SqlType::Interval
+            | SqlType::Json
+            | SqlType::Enum { .. }
+            | SqlType::Other { .. } => String::from("String"),
+            SqlType::Binary { .. } if kotlin => String::from("ByteArray"),
+            SqlType::Binary { .. } => String::from("byte[]"),
+            SqlType::Date => String::from("LocalDate"),
+            SqlType::Time => String::from("LocalTime"),
+            SqlType::Timestamp { time_zone: true } => String::from("OffsetDateTime"),
+            SqlType::Timestamp { time_zone: false } => String::from("LocalDateTime"),
+            SqlType::Uuid => String::from("UUID"),
+            SqlType::Array { element } => format!("List<{}>", self.type_of(element, true)),
+        }
+    }
+
+    fn properties<'a>(&self, model: &'a Model) -> Vec<Property<'a>> {
+        let table = &model.table;
+        let primary_key = table.primary_key();
+
+        table
+            .columns
+            .iter()
+            .map(|column| {
+                let nullable = column.nullable || column.generated;
+                let mut annotations = vec![];
+                if self.jpa {
+                    if primary_key.contains(&column.name.as_str()) {
+                        annotations.push(String::from("@Id"));
+                        if column.generated {
+                            annotations.push(String::from(
+                                "@GeneratedValue(strategy = GenerationType.IDENTITY)",
+                            ));
+                        }
+                    }
+
+                    let mut attributes = vec![format!("name = {}", self.string(&column.name))];
+                    if !column.nullable {
+                        attributes.push(String::from("nullable = false"));
+                    }
+                    if let (Some(max), SqlType::Char { .. } | SqlType::VarChar { .. }) =
+                        (column.sql_type.max_length(), &column.sql_type)
+                    {
+                        attributes.push(format!("length = {}", max.value));
+                    }
+                    if table.is_unique(&column.name) {
+                        attributes.push(String::from("unique = true"));
+                    }
+                    annotations.push(format!("@Column({})", attributes.join(", ")));
+                }
+
+                Property {
+                    column,
+                    name: self.identifier(&column.name.to_snake_case().to_camel_case()),
+                    type_name: self.type_of(&column.sql_type, nullable),
+                    nullable,
+                    annotations,
+                }
+            })
+            .collect()
+    }
+
+    /// Quotes `s` as a double-quoted string literal.
+    fn string(&self, s: &str) -> String {
+        match self.language {
+            // `$` starts a string template in Kotlin.
+            Language::Kotlin => string_literal(s, '"').replace('$', "\\$"),
+            Language::Java => string_literal(s, '"'),
+        }
+    }
+
+    fn identifier(&self, name: &str) -> String {
+        let name = match name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            true => name.to_string(),
+            false => format!("_{name}"),
+        };
+
+        match self.language {
+            Language::Kotlin if KOTLIN_KEYWORDS.contains(&name.as_str()) => format!("`{name}`"),
+            Language::Java if JAVA_KEYWORDS.contains(&name.as_str()) => format!("{name}_"),
+            _ => name,
+        }
+    }
+
+    fn class_annotations(&self, model: &Model) -> String {
+        if !self.jpa {
+            return String::new();
+        }
+
+        let mut table = vec![format!("name = {}", self.string(&model.table.name))];
+        if !model.table.schema.is_empty() {
+            table.push(format!(
+                "schema = {}",
+                self.string(&model.table.schema.join("."))
+            ));
+        }
+
+        format!("@Entity\n@Table({})\n", table.join(", "))
+    }
+
+    fn kotlin_class(&self, model: &Model) -> String {
+        let mut body = String::new();
+        for property in self.properties(model) {
+            if let Some(comment) = &property.column.comment {
+                body.push_str(&CommentStyle::BLOCK.render(comment.lines(), "    "));
+            }
+            for annotation in &property.annotations {
+                body.push_str(&format!("    {annotation}\n"));
+            }
+
+            let (null, default) = match (property.nullable, property.column.generated) {
+                (true, true) => ("?", " = null"),
+                (true, false) => ("?", ""),
+                (false, _) => ("", ""),
+            };
+            body.push_str(&format!(
+                "    val {}: {}{null}{default},\n",
+                property.name, property.type_name
+            ));
+        }
+
+        format!(
+            "{}data class {}(\n{body})\n",
+            self.class_annotations(model),
+            model.class_name
+        )
+    }
+
+    fn java_record(&self, model: &Model) -> String {
+        let components: Vec<String> = self
+            .properties(model)
+            .into_iter()
+            .map(|property| {
+                let doc = match &property.column.comment {
+                    Some(comment) => CommentStyle::BLOCK.render(comment.lines(), "    "),
+                    None => String::new(),
+                };
+                format!("{doc}    {} {}", property.type_name, property.name)
+            })
+            .collect();
+
+        format!(
+            "public record {}(\n{}\n) {{\n}}\n",
+            model.class_name,
+            components.join(",\n")
+        )
+    }
+
+    fn java_entity(&self, model: &Model) -> String {
+        let properties = self.properties(model);
+        let class_name = &model.class_name;
+
+        let mut fields = String::new();
+        let mut accessors = String::new();
+        for property in &properties {
+            if let Some(comment) = &property.column.comment {
+                fields.push_str(&CommentStyle::BLOCK.render(comment.lines(), "    "));
+            }
+            for annotation in &property.annotations {
+                fields.push_str(&format!("    {annotation}\n"));
+            }
+            fields.push_str(&format!(
+                "    private {} {};\n\n",
+                property.type_name, property.name
+            ));
+
+            // Keeps the `_` that escapes a keyword, so a `class` column gets
+            // `getClass_()` instead of clashing with `Object.getClass()`.
+            let mut accessor = property.column.name.to_snake_case().to_pascal_case();
+            if property
+                .name
+                .strip_suffix('_')
+                .is_some_and(|name| JAVA_KEYWORDS.contains(&name))
+            {
+                accessor.push('_');
+            }
+            accessors.push_str(&format!(
+                "\n    public {0} get{accessor}() {{\n        return {1};\n    }}\n\n    public void set{accessor}({0} {1}) {{\n        this.{1} = {1};\n    }}\n",
+                property.type_name, property.name
+            ));
+        }
+
+        format!(
+            "{}public class {class_name} {{\n{fields}    protected {class_name}() {{\n    }}\n{accessors}}}\n",
+            self.class_annotations(model)
+        )
+    }
+}
+
+impl Backend for Jvm {
+    fn file_name(&self, model: &Model) -> PathBuf {
+        let extension = match self.language {
+            Language::Kotlin => "kt",
+            Language::Java => "java",
+        };
+
+        // Java requires the file to be named after its public class.
+        let mut path: PathBuf = self.subpackages(model).into_iter().collect();
+        path.push(format!("{}.{extension}", model.class_name));
+
+        path
+    }
+
+    fn preamble(&self, model: &Model) -> String {
+        let package = [self.package.clone()]
+            .into_iter()
+            .chain(self.subpackages(model))
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(".");
+
+        let mut imports = BTreeSet::new();
+        for property in self.properties(model) {
+            for (name, import) in [
+                ("BigDecimal", "java.math.BigDecimal"),
+                ("BigInteger", "java.math.BigInteger"),
+                ("LocalDate", "java.time.LocalDate"),
+                ("LocalDateTime", "java.time.LocalDateTime"),
+                ("LocalTime", "java.time.LocalTime"),
+                ("OffsetDateTime", "java.time.OffsetDateTime"),
+                ("UUID", "java.util.UUID"),
+            ] {
+                if property
+                    .type_name
+                    .split(['<', '>'])
+                    .any(|part| part == name)
+                {
+                    imports.insert(import);
+                }
+            }
+            if self.language == Language::Java && property.type_name.starts_with("List<") {
+                imports.insert("java.util.List");
+            }
+            for annotation in &property.annotations {
+                for (name, import) in [
+                    ("@Id", "jakarta.persistence.Id"),
+                    ("@GeneratedValue", "jakarta.persistence.GeneratedValue"),
+                    ("GenerationType.", "jakarta.persistence.GenerationType"),
+                    ("@Column", "jakarta.persistence.Column"),
+                ] {
+                    if annotation.contains(name) {
+                        imports.insert(import);
+                    }
+                }
+            }
+        }
+        if self.jpa {
+            imports.insert("jakarta.persistence.Entity");
+            imports.insert("jakarta.persistence.Table");
+        }
+
+        let semicolon = match self.language {
+            Language::Kotlin => "",
+            Language::Java => ";",
+        };
+        let mut preamble = format!("// Generated by quaff from {}.sql.\n\n", model.stem);
+        if !package.is_empty() {
+            preamble.push_str(&format!("package {package}{semicolon}\n\n"));
+        }
+        if !imports.is_empty() {
+            for import in imports {
+                preamble.push_str(&format!("import {import}{semicolon}\n"));
+            }
+            preamble.push('\n');
+        }
+
+        preamble
+    }
+
+    fn type_name(&self, column: &Column) -> String {
+        self.type_of(&column.sql_type, column.nullable)
+    }
+
+    fn render(&self, model: &Model) -> anyhow::Result<String> {
+        let doc = match &model.table.comment {
+            Some(comment) => CommentStyle::BLOCK.render(comment.lines(), ""),
+            None => String::new(),
+        };
+
+        let class = match (self.language, self.jpa) {
+            (Language::Kotlin, _) => self.kotlin_class(model),
+            (Language::Java, false) => self.java_record(model),
+            (Language::Java, true) => self.java_entity(model),
+        };
+
+        Ok(format!("{doc}{class}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lang::testing::render;
+
+    const KOTLIN: &str = "lang = \"kotlin\"";
+    const JAVA: &str = "lang = \"java\"";
+
+    #[test]
+    fn keywords_are_escaped() {
+        let sql = "CREATE TABLE t (\"class\" TEXT NOT NULL, \"when\" TEXT NOT NULL)";
+
+        let code = render(KOTLIN, sql);
+        assert!(code.contains("    val `class`: String,\n"));
+        assert!(code.contains("    val `when`: String,\n"));
+
+        let code = render(JAVA, sql);
+        assert!(code.contains("    String class_,\n"));
+        assert!(code.contains("    String when\n"));
+
+        let code = render("lang = \"java\"\n[jvm]\njpa = true", sql);
+        assert!(code.contains("    public String getClass_() {\n"));
+    }
+
+    #[test]
+    fn unsigned_integers_widen() {
+        let sql = "CREATE TABLE t (
+            a TINYINT UNSIGNED NOT NULL,
+            b INT UNSIGNED NOT NULL,
+            c BIGINT UNSIGNED NOT NULL
+        )";
+
+        let code = render(JAVA, sql);
+        assert!(code.contains("    short a,\n"));
+        assert!(code.contains("    long b,\n"));
+        assert!(code.contains("    BigInteger c\n"));
+
+        let code = render(KOTLIN, sql);
+        assert!(code.contains("    val a: Short,\n"));
+        assert!(code.contains("    val b: Long,\n"));
+    }
+
+    #[test]
+    fn generated_keys_are_nullable() {
+        let sql = "CREATE TABLE t (id BIGINT AUTO_INCREMENT PRIMARY KEY, n INT)";
+
+        let code = render(KOTLIN, sql);
+        assert!(code.contains("    val id: Long? = null,\n"));
+        assert!(code.contains("    val n: Int?,\n"));
+
+        let code = render(JAVA, sql);
+        assert!(code.contains("    Long id,\n"));
+        assert!(code.contains("    Integer n\n"));
+    }
+}
@@ -1,4 +1,127 @@
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::cli::config::{Config, LangOption};
+use crate::sql::ir::{Column, Table};
+use crate::sql::query::NamedQuery;
+
+pub mod csharp;
+pub mod dump;
+pub mod go;
+pub mod inflector;
+pub mod json_schema;
+pub mod jvm;
 pub mod php;
+pub mod python;
+pub mod rust;
+#[cfg(test)]
+pub mod testing;
+pub mod typescript;
+
+/// A table together with the names it is generated under.
+pub struct Model {
+    pub table: Table,
+    /// The schema file's name without `.sql`.
+    pub stem: String,
+    pub class_name: String,
+}
+
+/// A generated file, relative to the output directory.
+pub struct File {
+    pub path: PathBuf,
+    pub contents: String,
+}
+
+/// A code generator for one target language.
+pub trait Backend {
+    /// Where the model for `model` is written, relative to the output
+    /// directory.
+    fn file_name(&self, model: &Model) -> PathBuf;
+
+    /// The code the model file starts with, e.g. `<?php` and a namespace.
+    fn preamble(&self, model: &Model) -> String;
+
+    /// The type `column` maps to, leaving nullability aside.
+    fn type_name(&self, column: &Column) -> String;
+
+    /// Renders the class for `model`, without the preamble.
+    fn render(&self, model: &Model) -> anyhow::Result<String>;
+
+    /// Files generated on top of one per model, such as repositories and
+    /// classes for the query files, given as `(stem, queries)` pairs.
+    fn extra_files(
+        &self,
+        _models: &[Model],
+        _queries: &[(String, Vec<NamedQuery>)],
+    ) -> anyhow::Result<Vec<File>> {
+        Ok(vec![])
+    }
+
+    /// Whether `extra_files` generates code for the query files.
+    fn renders_queries(&self) -> bool {
+        false
+    }
+
+    /// The model file for `model`, or `None` when the backend bundles every
+    /// model into one of its extra files.
+    fn model_file(&self, model: &Model) -> anyhow::Result<Option<File>> {
+        Ok(Some(File {
+            path: self.file_name(model),
+            contents: format!("{}{}", self.preamble(model), self.render(model)?),
+        }))
+    }
+}
+
+/// The backend for the language selected by `config.lang`.
+pub fn backend(config: &Config) -> anyhow::Result<Box<dyn Backend>> {
+    match LangOption::from_str(&config.lang)? {
+        LangOption::Php => Ok(Box::new(php::Php::new(config)?)),
+        LangOption::TypeScript => Ok(Box::new(typescript::TypeScript::new(config)?)),
+        LangOption::Rust => Ok(Box::new(rust::Rust::new(config)?)),
+        LangOption::Go => Ok(Box::new(go::Go::new(config)?)),
+        LangOption::Python => Ok(Box::new(python::Python::new(config)?)),
+        LangOption::Kotlin => Ok(Box::new(jvm::Jvm::new(config, jvm::Language::Kotlin)?)),
+        LangOption::Java => Ok(Box::new(jvm::Jvm::new(config, jvm::Language::Java)?)),
+        LangOption::CSharp => Ok(Box::new(csharp::CSharp::new(config)?)),
+        LangOption::JsonSchema => Ok(Box::new(json_schema::JsonSchema::new(
+            config,
+            json_schema::Output::Document,
+        )?)),
+        LangOption::OpenApi => Ok(Box::new(json_schema::JsonSchema::new(
+            config,
+            json_schema::Output::OpenApi,
+        )?)),
+    }
+}
+
+/// Builds a model for each file in `dir`, ordered by file name.
+pub fn load_models(dir: &Path, config: &Config) -> anyhow::Result<Vec<Model>> {
+    crate::sql::load_tables(dir)?
+        .into_iter()
+        .map(|(stem, table)| {
+            Ok(Model {
+                class_name: config.class_name(&table.schema, &table.name)?,
+                table,
+                stem,
+            })
+        })
+        .collect()
+}
+
+/// Every file `backend` generates for `models` and the query files.
+pub fn generate(
+    backend: &dyn Backend,
+    models: &[Model],
+    queries: &[(String, Vec<NamedQuery>)],
+) -> anyhow::Result<Vec<File>> {
+    let mut files = vec![];
+    for model in models {
+        files.extend(backend.model_file(model)?);
+    }
+    files.extend(backend.extra_files(models, queries)?);
+
+    Ok(files)
+}
 
 trait CamelCase {
     fn to_camel_case(&self) -> String;
@@ -59,3 +182,164 @@ impl PascalCase for str {
         to_pascal_case(self)
     }
 }
+
+trait SnakeCase {
+    fn to_snake_case(&self) -> String;
+}
+
+/// Lowercases `s` and separates its words with underscores, splitting on
+/// punctuation and camelCase humps.
+fn to_snake_case(s: &str) -> String {
+    let mut result = String::new();
+    let mut previous: Option<char> = None;
+
+    for c in s.chars() {
+        if !c.is_alphanumeric() {
+            previous = None;
+            continue;
+        }
+
+        let hump = c.is_uppercase() && previous.is_some_and(|p| p.is_lowercase() || p.is_numeric());
+        if !result.is_empty() && (previous.is_none() || hump) {
+            result.push('_');
+        }
+        result.extend(c.to_lowercase());
+        previous = Some(c);
+    }
+
+    result
+}
+
+impl SnakeCase for str {
+    fn to_snake_case(&self) -> String {
+        to_snake_case(self)
+    }
+}
+
+/// Builds the class name for `table`, singularizing the table name first when
+/// `singularize` is set.
+pub fn class_name(table: &str, singularize: bool) -> String {
+    if singularize {
+        inflector::singularize(table).to_pascal_case()
+    } else {
+        table.to_pascal_case()
+    }
+}
+
+/// The delimiters of a doc comment in a target language.
+struct CommentStyle {
+    /// The line opening the comment, e.g. `/**`.
+    open: Option<&'static str>,
+    /// Starts each line of text, e.g. ` *` or `///`.
+    prefix: &'static str,
+    /// The line closing the comment, e.g. ` */`.
+    close: Option<&'static str>,
+    /// Whether a single line of text goes between `open` and `close` on one
+    /// line, as in `/** Text */`.
+    inline: bool,
+    /// Makes a line of text safe to put in the comment.
+    escape: fn(&str) -> String,
+}
+
+impl CommentStyle {
+    /// `/** ... */` blocks: JSDoc, Javadoc, KDoc and PHPDoc.
+    const BLOCK: Self = Self {
+        open: Some("/**"),
+        prefix: " *",
+        close: Some(" */"),
+        inline: true,
+        // A literal `*/` would close the comment early.
+        escape: |line| line.replace("*/", "*\\/"),
+    };
+
+    /// Line comments starting with `prefix`, e.g. `///`.
+    const fn lines(prefix: &'static str) -> Self {
+        Self {
+            open: None,
+            prefix,
+            close: None,
+            inline: false,
+            escape: str::to_string,
+        }
+    }
+
+    /// Renders `lines` as a doc comment indented by `indent`.
+    fn render<'a>(&self, lines: impl IntoIterator<Item = &'a str>, indent: &str) -> String {
+        let lines: Vec<String> = lines
+            .into_iter()
+            .map(|line| (self.escape)(line.trim_end()))
+            .collect();
+
+        if let (true, Some(open), Some(close), [line]) =
+            (self.inline, self.open, self.close, lines.as_slice())
+        {
+            return format!("{indent}{open} {line}{close}\n");
+        }
+
+        let mut doc = String::new();
+        if let Some(open) = self.open {
+            doc.push_str(&format!("{indent}{open}\n"));
+        }
+        for line in lines {
+            match line.is_empty() {
+                true => doc.push_str(&format!("{indent}{}\n", self.prefix)),
+                false => doc.push_str(&format!("{indent}{} {line}\n", self.prefix)),
+            }
+        }
+        if let Some(close) = self.close {
+            doc.push_str(&format!("{indent}{close}\n"));
+        }
+
+        doc
+    }
+}
+
+/// Quotes `s` as a string literal delimited by `quote`, escaping backslashes
+/// and `quote` itself with a backslash.
+fn string_literal(s: &str, quote: char) -> String {
+    let escaped = s
+        .replace('\\', "\\\\")
+        .replace(quote, &format!("\\{quote}"));
+
+    format!("{quote}{escaped}{quote}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_comments() {
+        assert_eq!(
+            CommentStyle::BLOCK.render(["Ends */ early"], "  "),
+            "  /** Ends *\\/ early */\n"
+        );
+        assert_eq!(
+            CommentStyle::BLOCK.render("First\n\nThird  ".lines(), ""),
+            "/**\n * First\n *\n * Third\n */\n"
+        );
+    }
+
+    #[test]
+    fn line_comments() {
+        assert_eq!(
+            CommentStyle::lines("///").render("One\n\nTwo".lines(), "    "),
+            "    /// One\n    ///\n    /// Two\n"
+        );
+    }
+
+    #[test]
+    fn string_literals() {
+        assert_eq!(string_literal(r"it's C:\", '\''), r"'it\'s C:\\'");
+        assert_eq!(string_literal(r#"say "hi""#, '"'), r#""say \"hi\"""#);
+    }
+
+    #[test]
+    fn case_conversions() {
+        assert_eq!("order_items".to_pascal_case(), "OrderItems");
+        assert_eq!("createdAt".to_snake_case(), "created_at");
+        assert_eq!("Order-Items".to_snake_case(), "order_items");
+        assert_eq!(class_name("people", true), "Person");
+        assert_eq!(class_name("people", false), "People");
+    }
+}
@@ -1,106 +1,258 @@
 use anyhow::anyhow;
-use sqlparser::ast::{ColumnOption, DataType, ExactNumberInfo, Statement};
+use sqlparser::ast::{Expr, UnaryOperator, Value};
+use std::path::PathBuf;
 use std::{fmt::Display, str::FromStr};
 use thiserror::Error;
 
-use crate::lang::PascalCase;
+use crate::cli::config::{ClassNames, Config, PhpFlavor, SchemaMode};
+use crate::lang::{
+    Backend, CamelCase, CommentStyle, File, Model, PascalCase, inflector, string_literal,
+};
+use crate::sql::dialect::Dialect;
+use crate::sql::ir::{self, MaxLength, Table};
+use crate::sql::query::NamedQuery;
+use check::Check;
+use doctrine::Doctrine;
+use eloquent::Eloquent;
+pub use query::{Catalog, Queries};
+pub use repository::Repository;
+
+mod check;
+mod doctrine;
+mod eloquent;
+pub mod migration;
+mod query;
+mod repository;
+
+/// Generates PHP models, and optionally PDO repositories, into the configured
+/// namespace (`Quaff` by default).
+pub struct Php {
+    /// The root namespace of generated classes.
+    namespace: String,
+    flavor: PhpFlavor,
+    schema_mode: SchemaMode,
+    guards: bool,
+    repositories: bool,
+    dialect: Dialect,
+    /// Resolves the class names of referenced tables.
+    class_names: ClassNames,
+}
+
+impl Php {
+    pub fn new(config: &Config) -> anyhow::Result<Self> {
+        let flavor = PhpFlavor::from_str(&config.php.flavor)?;
+        if config.repositories && !matches!(flavor, PhpFlavor::Plain) {
+            return Err(anyhow!(
+                "repositories are only generated for plain PHP models"
+            ));
+        }
+
+        Ok(Self {
+            namespace: config.php.namespace.clone(),
+            flavor,
+            schema_mode: SchemaMode::from_str(&config.schema_mode)?,
+            guards: config.guards,
+            repositories: config.repositories,
+            dialect: Dialect::from_str(&config.dialect)?,
+            class_names: config.class_names()?,
+        })
+    }
+
+    fn class(&self, model: &Model) -> Class {
+        let mut class = Class::new(&model.table);
+        class.class_name = model.class_name.clone();
+        class.guards = self.guards;
+        class.namespace = self.namespace_of(&class.schema);
+
+        class
+    }
+
+    /// The namespace of the classes generated for tables in `schema`.
+    fn namespace_of(&self, schema: &[String]) -> String {
+        let mut namespace = self.namespace.clone();
+        if let SchemaMode::Namespace = self.schema_mode {
+            for part in schema {
+                namespace.push('\\');
+                namespace.push_str(&part.to_pascal_case());
+            }
+        }
+
+        namespace
+    }
+
+    /// The model of the table named `table`, as referenced from `namespace`.
+    fn related_class(&self, table: &[String], namespace: &str) -> anyhow::Result<String> {
+        let (name, schema) = table
+            .split_last()
+            .ok_or(anyhow!("foreign key references an empty table name"))?;
+        let class_name = self.class_names.class_name(schema, name);
+
+        match self.namespace_of(schema) {
+            related if related == namespace => Ok(class_name),
+            related => Ok(format!("\\{related}\\{class_name}")),
+        }
+    }
+
+    /// The directory of `class`, relative to the output directory: one level
+    /// per sub-namespace.
+    fn class_dir(&self, class: &Class) -> PathBuf {
+        class
+            .namespace
+            .strip_prefix(&self.namespace)
+            .unwrap_or(&class.namespace)
+            .split('\\')
+            .filter(|part| !part.is_empty())
+            .collect()
+    }
+}
+
+/// Starts a PHP file in `namespace` that imports `imports`.
+fn preamble(namespace: &str, imports: &[&str]) -> String {
+    let imports: String = imports
+        .iter()
+        .map(|import| format!("use {import};\n"))
+        .collect();
+
+    format!("<?php\nnamespace {namespace};\n{imports}\n")
+}
+
+impl Backend for Php {
+    fn file_name(&self, model: &Model) -> PathBuf {
+        self.class_dir(&self.class(model))
+            .join(format!("{}.php", model.stem))
+    }
+
+    fn preamble(&self, model: &Model) -> String {
+        let namespace = self.class(model).namespace;
+        match self.flavor {
+            PhpFlavor::Plain => preamble(&namespace, &["DateTime"]),
+            PhpFlavor::Eloquent => preamble(&namespace, &Eloquent::imports(&model.table)),
+            PhpFlavor::Doctrine => preamble(&namespace, Doctrine::IMPORTS),
+        }
+    }
+
+    fn type_name(&self, column: &ir::Column) -> String {
+        Kind::from(&column.sql_type).to_string()
+    }
+
+    fn render(&self, model: &Model) -> anyhow::Result<String> {
+        let class = self.class(model);
+        match self.flavor {
+            PhpFlavor::Plain => Ok(class.to_string()),
+            PhpFlavor::Eloquent => Ok(Eloquent::new(&model.table, &class.class_name, |table| {
+                self.related_class(table, &class.namespace)
+            })?
+            .to_string()),
+            PhpFlavor::Doctrine => Ok(Doctrine::new(&model.table, &class.class_name, |table| {
+                self.related_class(table, &class.namespace)
+            })?
+            .to_string()),
+        }
+    }
+
+    fn renders_queries(&self) -> bool {
+        true
+    }
+
+    fn extra_files(
+        &self,
+        models: &[Model],
+        queries: &[(String, Vec<NamedQuery>)],
+    ) -> anyhow::Result<Vec<File>> {
+        let classes: Vec<Class> = models.iter().map(|model| self.class(model)).collect();
+        let mut files = vec![];
+
+        if self.repositories {
+            for class in &classes {
+                // Named after the class, so PSR-4 autoloading finds it.
+                let repository = Repository::new(class, self.dialect);
+                files.push(File {
+                    path: self
+                        .class_dir(class)
+                        .join(format!("{}.php", repository.class_name())),
+                    contents: format!("{}{repository}", preamble(&class.namespace, &["DateTime"])),
+                });
+            }
+        }
+
+        // Doctrine entities are built by the entity manager, not from rows.
+        let models = !matches!(self.flavor, PhpFlavor::Doctrine);
+        let catalog = Catalog::new(&classes, &self.namespace, models);
+        let preamble = preamble(&self.namespace, &["DateTime"]);
+        for (stem, named) in queries {
+            // One class per file, named after it, as with repositories.
+            let queries = Queries::new(stem, named, &catalog)?;
+            for (class_name, row) in queries.rows() {
+                files.push(File {
+                    path: PathBuf::from(format!("{class_name}.php")),
+                    contents: format!("{preamble}{row}"),
+                });
+            }
+            files.push(File {
+                path: PathBuf::from(format!("{}.php", queries.class_name)),
+                contents: format!("{preamble}{queries}"),
+            });
+        }
+
+        Ok(files)
+    }
+}
 
 #[derive(Debug, Default)]
 pub struct Class {
     pub name: String,
+    /// Leading parts of a qualified table name, e.g. `billing` in `billing.invoices`.
+    pub schema: Vec<String>,
+    pub class_name: String,
+    /// The PHP namespace the model is written to.
+    pub namespace: String,
+    /// Table-level `COMMENT`, rendered as the class docblock.
+    pub comment: Option<String>,
+    /// Emit constructor guards for column lengths and unsigned integers.
+    pub guards: bool,
+    /// Primary key columns, in key order.
+    pub primary_key: Vec<String>,
     fields: Vec<Field>,
+    checks: Vec<Check>,
 }
 
 impl Class {
-    pub fn new(stmts: Vec<Statement>) -> anyhow::Result<Self> {
-        let mut class = Self::default();
-
-        for stmt in stmts {
-            if let Statement::CreateTable(table) = stmt {
-                class.name = table.name.0.get(0).unwrap().to_string();
-
-                let mut fields: Vec<Field> = vec![];
-
-                for column in table.columns {
-                    column.options.iter().for_each(|f| match &f.option {
-                        ColumnOption::Unique {
-                            is_primary,
-                            characteristics,
-                        } => {
-                            _ = characteristics;
-                            if *is_primary {
-                                fields.push(Field {
-                                    visibility: Visibilty::Private,
-                                    type_annotation: Kind::from(&column.data_type),
-                                    name: column.name.to_string(),
-                                    nullable: false,
-                                });
-                            }
-                        }
-                        ColumnOption::Null => {
-                            fields.push(Field {
-                                visibility: Visibilty::Public,
-                                type_annotation: Kind::from(&column.data_type),
-                                name: column.name.to_string(),
-                                nullable: true,
-                            });
-                        }
-                        ColumnOption::NotNull => {
-                            fields.push(Field {
-                                visibility: Visibilty::Public,
-                                type_annotation: Kind::from(&column.data_type),
-                                name: column.name.to_string(),
-                                nullable: false,
-                            });
-                        }
-                        _ => {} // ColumnOption::Default(expr) => todo!(),
-                                // ColumnOption::Materialized(expr) => todo!(),
-                                // ColumnOption::Ephemeral(expr) => todo!(),
-                                // ColumnOption::Alias(expr) => todo!(),
-                                // ColumnOption::ForeignKey {
-                                //     foreign_table,
-                                //     referred_columns,
-                                //     on_delete,
-                                //     on_update,
-                                //     characteristics,
-                                // } => todo!("Hook into other files to build relationships"),
-                                // ColumnOption::Check(expr) => todo!(),
-                                // ColumnOption::DialectSpecific(tokens) => todo!(),
-                                // ColumnOption::CharacterSet(object_name) => todo!(),
-                                // ColumnOption::Collation(object_name) => todo!(),
-                                // ColumnOption::Comment(_) => todo!(),
-                                // ColumnOption::OnUpdate(expr) => todo!(),
-                                // ColumnOption::Generated {
-                                //     generated_as,
-                                //     sequence_options,
-                                //     generation_expr,
-                                //     generation_expr_mode,
-                                //     generated_keyword,
-                                // } => todo!(),
-                                // ColumnOption::Options(sql_options) => todo!(),
-                                // ColumnOption::Identity(identity_property_kind) => todo!(),
-                                // ColumnOption::OnConflict(keyword) => todo!(),
-                                // ColumnOption::Policy(column_policy) => todo!(),
-                                // ColumnOption::Tags(tags_column_option) => todo!(),
-                    });
-                }
+    pub fn new(table: &Table) -> Self {
+        let primary_key: Vec<String> = table.primary_key().into_iter().map(String::from).collect();
+        let fields: Vec<Field> = table
+            .columns
+            .iter()
+            .map(|column| Field::new(column, primary_key.contains(&column.name)))
+            .collect();
 
-                class.fields = fields;
-            } else {
-                return Err(anyhow!("SQL must be a create table statment"));
-            }
+        Self {
+            name: table.name.clone(),
+            schema: table.schema.clone(),
+            class_name: table.name.to_pascal_case(),
+            namespace: String::new(),
+            comment: table.comment.clone(),
+            guards: false,
+            primary_key,
+            checks: table
+                .checks()
+                .map(|(name, expr)| Check::new(name.map(String::from), expr, &fields))
+                .collect(),
+            fields,
         }
-
-        Ok(class)
     }
 }
 
 impl Display for Class {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // PHP requires optional parameters to follow required ones.
+        let mut parameters: Vec<&Field> = self.fields.iter().collect();
+        parameters.sort_by_key(|field| field.default.is_some());
+
         let mut fields = String::new();
 
-        let len = self.fields.len();
-        for (i, field) in self.fields.iter().enumerate() {
+        let len = parameters.len();
+        for (i, field) in parameters.into_iter().enumerate() {
+            fields.push_str(&field.doc_block());
             if i + 1 == len {
                 // no trailing comma
                 fields.push_str(&format!("        {field}\n"));
@@ -109,10 +261,129 @@ impl Display for Class {
             }
         }
 
+        let doc = match &self.comment {
+            Some(comment) => CommentStyle::BLOCK.render(comment.lines(), ""),
+            None => String::new(),
+        };
+
         write!(
             f,
-            "class {} {{\n    public function __construct(\n{fields}    ) {{ }}\n}}",
-            self.name.to_pascal_case()
+            "{doc}class {} {{\n    public function __construct(\n{fields}    ) {}\n\n{}{}{}{}}}",
+            self.class_name,
+            self.constructor_body(),
+            self.hydrate_method(),
+            self.getters(),
+            self.insert_payload(),
+            self.validate_method()
+        )
+    }
+}
+
+impl Class {
+    /// Renders `fromRow()`, which builds the model from a row fetched with
+    /// `PDO::FETCH_ASSOC`.
+    fn hydrate_method(&self) -> String {
+        let args: String = self
+            .fields
+            .iter()
+            .map(|field| {
+                format!(
+                    "            {}: {},\n",
+                    field.name,
+                    field.hydrate_value(&format!("$row['{}']", field.name))
+                )
+            })
+            .collect();
+
+        format!(
+            "    /** @param array<string, mixed> $row */\n    public static function fromRow(array $row): self {{\n        return new self(\n{args}        );\n    }}\n\n"
+        )
+    }
+
+    /// Renders read accessors for private properties such as primary keys.
+    fn getters(&self) -> String {
+        self.fields
+            .iter()
+            .filter(|field| matches!(field.visibility, Visibilty::Private))
+            .map(|field| {
+                let null = if field.holds_null() { "?" } else { "" };
+                format!(
+                    "    public function {}(): {null}{} {{\n        return $this->{};\n    }}\n\n",
+                    field.getter_name(),
+                    field.type_annotation,
+                    field.name
+                )
+            })
+            .collect()
+    }
+
+    /// Renders the constructor body. With `guards` enabled it rejects values
+    /// the database would refuse: strings over the column length and negative
+    /// numbers in unsigned columns. `NOT NULL` is already enforced by the
+    /// non-nullable parameter types.
+    fn constructor_body(&self) -> String {
+        let guards: Vec<String> = match self.guards {
+            true => self.fields.iter().flat_map(Field::guards).collect(),
+            false => vec![],
+        };
+
+        if guards.is_empty() {
+            return String::from("{ }");
+        }
+
+        format!("{{\n{}    }}", guards.concat())
+    }
+
+    /// Renders `validate()` from the table's `CHECK` constraints. It returns
+    /// one message per violated constraint. Constraints that couldn't be
+    /// translated are kept as comments.
+    fn validate_method(&self) -> String {
+        if self.checks.is_empty() {
+            return String::new();
+        }
+
+        let mut body = String::new();
+        for check in &self.checks {
+            match &check.condition {
+                Some(condition) => body.push_str(&format!(
+                    "        if (!({condition})) {{\n            $violations[] = {};\n        }}\n",
+                    php_string(&check.message())
+                )),
+                None => body.push_str(&format!(
+                    "        // Untranslated check constraint: {}\n",
+                    check.sql.replace('\n', " ")
+                )),
+            }
+        }
+
+        format!(
+            "\n    /** @return string[] */\n    public function validate(): array {{\n        $violations = [];\n{body}\n        return $violations;\n    }}\n"
+        )
+    }
+
+    /// Renders `toInsertArray()`, which maps the caller-supplied columns to
+    /// their values. Database-generated columns are left out, as are columns
+    /// with a database default while they are still `null`.
+    fn insert_payload(&self) -> String {
+        let mut required = String::new();
+        let mut optional = String::new();
+
+        for field in self.fields.iter().filter(|field| !field.generated) {
+            if let Some(DefaultValue::Generated) = field.default {
+                // Only reached once the value is known not to be null.
+                let value = field.insert_value(false);
+                optional.push_str(&format!(
+                    "        if ($this->{0} !== null) {{\n            $row['{0}'] = {value};\n        }}\n",
+                    field.name
+                ));
+            } else {
+                let value = field.insert_value(field.holds_null());
+                required.push_str(&format!("            '{}' => {value},\n", field.name));
+            }
+        }
+
+        format!(
+            "    public function toInsertArray(): array {{\n        $row = [\n{required}        ];\n{optional}\n        return $row;\n    }}\n"
         )
     }
 }
@@ -123,20 +394,336 @@ pub struct Field {
     pub type_annotation: Kind,
     pub name: String,
     pub nullable: bool,
+    /// Callers may leave the value out for the database to fill in on insert,
+    /// so the property is `null` until the row is read back.
+    pub optional: bool,
+    pub default: Option<DefaultValue>,
+    /// Assigned by the database: identity, auto-increment and computed columns.
+    pub generated: bool,
+    /// The column type as written in the schema, e.g. `VARCHAR(255)`.
+    pub sql_type: String,
+    pub comment: Option<String>,
+    pub max_length: Option<MaxLength>,
+    pub unsigned: bool,
+    /// `DECIMAL` and `NUMERIC` columns, carried as numeric strings.
+    pub exact_numeric: bool,
+}
+
+/// Converts the PHP value `value` into something PDO can bind.
+fn to_sql_value(kind: &Kind, json: bool, nullable: bool, value: &str) -> String {
+    let converted = match kind {
+        Kind::DateTime => format!("{value}->format('Y-m-d H:i:s')"),
+        // PDO binds `false` as an empty string, which typed columns reject.
+        Kind::Bool => format!("(int) {value}"),
+        Kind::Array if json => format!("json_encode({value})"),
+        _ => return value.to_string(),
+    };
+
+    match nullable {
+        true => format!("{value} === null ? null : {converted}"),
+        false => converted,
+    }
+}
+
+/// Converts the raw value `value` fetched through PDO into `kind`. Drivers
+/// commonly return every column as a string.
+fn from_sql_value(kind: &Kind, json: bool, nullable: bool, value: &str) -> String {
+    let converted = match kind {
+        Kind::Int => format!("(int) {value}"),
+        Kind::Float => format!("(float) {value}"),
+        Kind::Bool => format!("(bool) {value}"),
+        Kind::DateTime => format!("new DateTime({value})"),
+        Kind::Array if json => format!("json_decode({value}, true)"),
+        _ => return value.to_string(),
+    };
+
+    match nullable {
+        true => format!("{value} === null ? null : {converted}"),
+        false => converted,
+    }
+}
+
+/// Renders an `if` that throws an `InvalidArgumentException` when `condition`
+/// holds.
+fn guard(condition: &str, message: &str) -> String {
+    format!(
+        "        if ({condition}) {{\n            throw new \\InvalidArgumentException({});\n        }}\n",
+        php_string(message)
+    )
+}
+
+impl Field {
+    fn new(column: &ir::Column, primary_key: bool) -> Self {
+        let kind = Kind::from(&column.sql_type);
+        let default = match (column.generated, &column.default) {
+            // Identity and computed columns are assigned by the database and
+            // never supplied by the caller.
+            (true, _) => Some(DefaultValue::Generated),
+            (false, Some(expr)) => Some(DefaultValue::new(expr, &kind)),
+            (false, None) => None,
+        };
+        let optional = matches!(default, Some(DefaultValue::Generated | DefaultValue::Null));
+
+        Self {
+            visibility: match primary_key {
+                true => Visibilty::Private,
+                false => Visibilty::Public,
+            },
+            type_annotation: kind,
+            name: column.name.clone(),
+            nullable: column.nullable,
+            optional,
+            default,
+            generated: column.generated,
+            sql_type: column.data_type.to_string(),
+            comment: column.comment.clone(),
+            max_length: column.sql_type.max_length(),
+            unsigned: column.sql_type.is_unsigned(),
+            exact_numeric: matches!(column.sql_type, ir::SqlType::Decimal { .. }),
+        }
+    }
+
+    /// Renders the `InvalidArgumentException` guards for this field.
+    fn guards(&self) -> Vec<String> {
+        let null_check = if self.holds_null() {
+            format!("$this->{} !== null && ", self.name)
+        } else {
+            String::new()
+        };
+
+        let mut guards = vec![];
+        if let (Some(max), Kind::String) = (&self.max_length, &self.type_annotation) {
+            let (strlen, unit) = match max.bytes {
+                true => ("strlen", "bytes"),
+                false => ("mb_strlen", "characters"),
+            };
+            guards.push(guard(
+                &format!("{null_check}{strlen}($this->{}) > {}", self.name, max.value),
+                &format!("{} must be at most {} {unit}", self.name, max.value),
+            ));
+        }
+
+        if self.unsigned && matches!(self.type_annotation, Kind::Int | Kind::Float) {
+            guards.push(guard(
+                &format!("{null_check}$this->{} < 0", self.name),
+                &format!("{} must not be negative", self.name),
+            ));
+        }
+
+        guards
+    }
+
+    /// Renders the PHPDoc for the promoted constructor parameter: the column
+    /// comment followed by an `@var` tag carrying the SQL type.
+    fn doc_block(&self) -> String {
+        let null = if self.holds_null() { "|null" } else { "" };
+        let var = format!("@var {}{null} {}", self.type_annotation, self.sql_type);
+
+        let mut lines: Vec<&str> = vec![];
+        if let Some(comment) = &self.comment {
+            lines.extend(comment.lines());
+            lines.push("");
+        }
+        lines.push(&var);
+
+        CommentStyle::BLOCK.render(lines, "        ")
+    }
+
+    /// The PHP expression used for this field in an insert payload. `nullable`
+    /// guards the conversion against a null value.
+    fn insert_value(&self, nullable: bool) -> String {
+        to_sql_value(
+            &self.type_annotation,
+            self.is_json(),
+            nullable,
+            &format!("$this->{}", self.name),
+        )
+    }
+
+    /// Converts the raw column value `value` into this field's PHP type.
+    fn hydrate_value(&self, value: &str) -> String {
+        from_sql_value(&self.type_annotation, self.is_json(), self.nullable, value)
+    }
+
+    /// Whether the property can hold `null`: the column is nullable, or its
+    /// value isn't known until the row is read back.
+    pub fn holds_null(&self) -> bool {
+        self.nullable || self.optional
+    }
+
+    /// The name of the generated getter, e.g. `getUserId` for `user_id`.
+    pub fn getter_name(&self) -> String {
+        format!("get{}", self.name.to_pascal_case())
+    }
+
+    fn is_json(&self) -> bool {
+        self.sql_type.to_uppercase().starts_with("JSON")
+    }
 }
 
 impl Display for Field {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let null = if self.nullable { "?" } else { "" };
+        let null = if self.holds_null() { "?" } else { "" };
+        let readonly = if self.generated { "readonly " } else { "" };
 
         write!(
             f,
-            "{} {}{} ${}",
+            "{} {readonly}{}{} ${}",
             self.visibility, null, self.type_annotation, self.name
-        )
+        )?;
+
+        match &self.default {
+            Some(default) => write!(f, " = {default}"),
+            None => Ok(()),
+        }
     }
 }
 
+/// A column's `DEFAULT` clause as a PHP constructor parameter default.
+#[derive(Debug)]
+pub enum DefaultValue {
+    /// A PHP expression equivalent to the SQL literal.
+    Literal(String),
+    /// `DEFAULT NULL`.
+    Null,
+    /// The database computes the value on insert, e.g. `CURRENT_TIMESTAMP` or
+    /// `now()`. The parameter defaults to `null` so the column can be omitted.
+    Generated,
+}
+
+impl DefaultValue {
+    pub fn new(expr: &Expr, kind: &Kind) -> Self {
+        let (value, negative) = match expr {
+            Expr::Value(v) => (&v.value, false),
+            Expr::UnaryOp {
+                op: UnaryOperator::Minus,
+                expr,
+            } => match expr.as_ref() {
+                Expr::Value(v) => (&v.value, true),
+                _ => return Self::Generated,
+            },
+            Expr::Nested(expr) => return Self::new(expr, kind),
+            _ => return Self::Generated,
+        };
+
+        let literal = match value {
+            Value::Null => return Self::Null,
+            Value::Boolean(b) => match kind {
+                Kind::Bool | Kind::Mixed => Some(b.to_string()),
+                Kind::Int => Some(u8::from(*b).to_string()),
+                _ => None,
+            },
+            Value::Number(n, _) => {
+                let n = if negative { format!("-{n}") } else { n.clone() };
+                match kind {
+                    Kind::Int => n.parse::<i64>().ok().map(|n| n.to_string()),
+                    Kind::Float => n.parse::<f64>().ok().map(|_| n),
+                    Kind::Bool => match n.as_str() {
+                        "0" => Some(String::from("false")),
+                        "1" => Some(String::from("true")),
+                        _ => None,
+                    },
+                    Kind::String => Some(php_string(&n)),
+                    Kind::Mixed => Some(n),
+                    _ => None,
+                }
+            }
+            Value::SingleQuotedString(s)
+            | Value::DoubleQuotedString(s)
+            | Value::NationalStringLiteral(s)
+            | Value::EscapedStringLiteral(s) => match kind {
+                Kind::String | Kind::Mixed => Some(php_string(s)),
+                Kind::Int => s.trim().parse::<i64>().ok().map(|n| n.to_string()),
+                Kind::Float => s.trim().parse::<f64>().ok().map(|_| s.trim().to_string()),
+                Kind::Bool => match s.to_lowercase().as_str() {
+                    "0" | "f" | "false" | "n" | "no" => Some(String::from("false")),
+                    "1" | "t" | "true" | "y" | "yes" => Some(String::from("true")),
+                    _ => None,
+                },
+                Kind::DateTime => Some(format!("new DateTime({})", php_string(s))),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        match literal {
+            Some(literal) => Self::Literal(literal),
+            // Anything PHP can't express is left for the database to fill in.
+            None => Self::Generated,
+        }
+    }
+}
+
+impl Display for DefaultValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DefaultValue::Literal(literal) => write!(f, "{literal}"),
+            DefaultValue::Null | DefaultValue::Generated => write!(f, "null"),
+        }
+    }
+}
+
+/// The backed enum generated for the `ENUM` column `column` of `class_name`,
+/// e.g. `OrderStatus`.
+fn enum_name(class_name: &str, column: &ir::Column) -> String {
+    format!("{class_name}{}", column.name.to_pascal_case())
+}
+
+/// Renders a string-backed enum for each `ENUM` column of `table`.
+fn backed_enums(table: &Table, class_name: &str) -> String {
+    let mut enums = String::new();
+    for column in &table.columns {
+        let ir::SqlType::Enum { values } = &column.sql_type else {
+            continue;
+        };
+
+        let mut cases = String::new();
+        for value in values {
+            cases.push_str(&format!(
+                "    case {} = {};\n",
+                enum_case(value),
+                php_string(value)
+            ));
+        }
+        enums.push_str(&format!(
+            "enum {}: string {{\n{cases}}}\n\n",
+            enum_name(class_name, column)
+        ));
+    }
+
+    enums
+}
+
+/// The case of a generated backed enum holding `value`, e.g. `Active`.
+fn enum_case(value: &str) -> String {
+    let case = value.to_pascal_case();
+    match case.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        true => case,
+        false => format!("Value{case}"),
+    }
+}
+
+/// Names the relationship through `key` after its column without `_id`, or
+/// else the singular referenced table. Names in `taken` get the column
+/// appended, e.g. `userByEditor`.
+fn relation_name(key: &ir::ForeignKey, taken: &[&str]) -> Option<String> {
+    let column = key.columns.first()?;
+    let mut name = match (key.columns.len(), column.strip_suffix("_id")) {
+        (1, Some(stem)) if !stem.is_empty() => stem.to_camel_case(),
+        _ => inflector::singularize(key.foreign_table.last()?).to_camel_case(),
+    };
+    if taken.contains(&name.as_str()) {
+        name = format!("{name}By{}", column.to_pascal_case());
+    }
+
+    Some(name)
+}
+
+/// Quotes `s` as a single-quoted PHP string literal.
+fn php_string(s: &str) -> String {
+    string_literal(s, '\'')
+}
+
 #[derive(Debug)]
 pub enum Visibilty {
     Public,
@@ -168,7 +755,7 @@ impl FromStr for Visibilty {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum Kind {
     Int,
     Float,
@@ -223,171 +810,80 @@ impl FromStr for Kind {
             "null" => Ok(Kind::Null),
             "false" => Ok(Kind::False),
             "true" => Ok(Kind::True),
-            "DateTime" => Ok(Kind::DateTime),
+            "datetime" => Ok(Kind::DateTime),
             other => Err(TypeParseError::UnknownType(String::from(other))),
         }
     }
 }
 
-impl From<&DataType> for Kind {
-    fn from(value: &DataType) -> Self {
+impl From<&ir::SqlType> for Kind {
+    fn from(value: &ir::SqlType) -> Self {
         match value {
-            DataType::Table(_column_defs) => Kind::Array,
-            DataType::Character(_character_length) => Kind::String,
-            DataType::Char(_character_length) => Kind::String,
-            DataType::CharacterVarying(_character_length) => Kind::String,
-            DataType::CharVarying(_character_length) => Kind::String,
-            DataType::Varchar(_character_length) => Kind::String,
-            DataType::Nvarchar(_character_length) => Kind::String,
-            DataType::Uuid => Kind::String,
-            DataType::CharacterLargeObject(_) => Kind::String,
-            DataType::CharLargeObject(_) => Kind::String,
-            DataType::Clob(_) => Kind::String,
-            DataType::Binary(_) => Kind::Array,
-            DataType::Varbinary(_binary_length) => Kind::Array,
-            DataType::Blob(_) => Kind::Array,
-            DataType::TinyBlob => Kind::Array,
-            DataType::MediumBlob => Kind::Array,
-            DataType::LongBlob => Kind::Array,
-            DataType::Bytes(_) => Kind::Array,
-            DataType::Numeric(exact_number_info) => {
-                if let ExactNumberInfo::None = exact_number_info {
-                    return Kind::Int;
-                } else {
-                    return Kind::String;
-                }
-            }
-            DataType::Decimal(exact_number_info) => {
-                if let ExactNumberInfo::None = exact_number_info {
-                    return Kind::Float;
-                } else {
-                    return Kind::String;
-                }
-            }
-            DataType::BigNumeric(exact_number_info) => {
-                if let ExactNumberInfo::None = exact_number_info {
-                    return Kind::Int;
-                } else {
-                    return Kind::String;
-                }
-            }
-            DataType::BigDecimal(exact_number_info) => {
-                if let ExactNumberInfo::None = exact_number_info {
-                    return Kind::Float;
-                } else {
-                    return Kind::String;
-                }
-            }
-            DataType::Dec(exact_number_info) => {
-                if let ExactNumberInfo::None = exact_number_info {
-                    return Kind::Float;
-                } else {
-                    return Kind::String;
-                }
-            }
-            DataType::Float(_) => Kind::Float,
-            DataType::TinyInt(_) => Kind::Int,
-            DataType::TinyIntUnsigned(_) => Kind::Int,
-            DataType::UTinyInt => Kind::Int,
-            DataType::Int2(_) => Kind::Int,
-            DataType::Int2Unsigned(_) => Kind::Int,
-            DataType::SmallInt(_) => Kind::Int,
-            DataType::SmallIntUnsigned(_) => Kind::Int,
-            DataType::USmallInt => Kind::Int,
-            DataType::MediumInt(_) => Kind::Int,
-            DataType::MediumIntUnsigned(_) => Kind::Int,
-            DataType::Int(_) => Kind::Int,
-            DataType::Int4(_) => Kind::Int,
-            DataType::Int8(_) => Kind::Int,
-            DataType::Int16 => Kind::Int,
-            DataType::Int32 => Kind::Int,
-            DataType::Int64 => Kind::Int,
-            DataType::Int128 => Kind::Int,
-            DataType::Int256 => Kind::Int,
-            DataType::Integer(_) => Kind::Int,
-            DataType::IntUnsigned(_) => Kind::Int,
-            DataType::Int4Unsigned(_) => Kind::Int,
-            DataType::IntegerUnsigned(_) => Kind::Int,
-            DataType::HugeInt => Kind::Int,
-            DataType::UHugeInt => Kind::Int,
-            DataType::UInt8 => Kind::Int,
-            DataType::UInt16 => Kind::Int,
-            DataType::UInt32 => Kind::Int,
-            DataType::UInt64 => Kind::Int,
-            DataType::UInt128 => Kind::Int,
-            DataType::UInt256 => Kind::Int,
-            DataType::BigInt(_) => Kind::Int,
-            DataType::BigIntUnsigned(_) => Kind::Int,
-            DataType::UBigInt => Kind::Int,
-            DataType::Int8Unsigned(_) => Kind::Int,
-            DataType::Signed => Kind::Int,
-            DataType::SignedInteger => Kind::Int,
-            DataType::Unsigned => Kind::Int,
-            DataType::UnsignedInteger => Kind::Int,
-            DataType::Float4 => Kind::Float,
-            DataType::Float32 => Kind::Float,
-            DataType::Float64 => Kind::Float,
-            DataType::Real => Kind::Float,
-            DataType::Float8 => Kind::Float,
-            DataType::Double(exact_number_info) => {
-                if let ExactNumberInfo::None = exact_number_info {
-                    return Kind::Float;
-                } else {
-                    return Kind::String;
-                }
-            }
-            DataType::DoublePrecision => Kind::Float,
-            DataType::Bool => Kind::Bool,
-            DataType::Boolean => Kind::Bool,
-            DataType::Date => Kind::DateTime,
-            DataType::Date32 => Kind::DateTime,
-            DataType::Time(_, _timezone_info) => Kind::DateTime,
-            DataType::Datetime(_) => Kind::DateTime,
-            DataType::Datetime64(_, _) => Kind::DateTime,
-            DataType::Timestamp(_, _timezone_info) => Kind::DateTime,
-            DataType::TimestampNtz => Kind::DateTime,
-            DataType::Interval => Kind::String,
-            DataType::JSON => Kind::String,
-            DataType::JSONB => Kind::Array,
-            DataType::Regclass => Kind::String,
-            DataType::Text => Kind::String,
-            DataType::TinyText => Kind::String,
-            DataType::MediumText => Kind::String,
-            DataType::LongText => Kind::String,
-            DataType::String(_) => Kind::String,
-            DataType::FixedString(_) => Kind::String,
-            DataType::Bytea => Kind::String,
-            DataType::Bit(_) => Kind::String,
-            DataType::BitVarying(_) => Kind::String,
-            DataType::VarBit(_) => Kind::String,
-            // Need to add better parsing for this at a later date.
-            DataType::Custom(_object_name, _items) => Kind::Mixed,
-            DataType::Array(_array_elem_type_def) => Kind::Mixed,
-            DataType::Map(_data_type, _data_type1) => Kind::Mixed,
-            DataType::Tuple(_struct_fields) => Kind::Mixed,
-            DataType::Nested(_column_defs) => Kind::Mixed,
-            DataType::Enum(_enum_members, _) => Kind::Mixed,
-            DataType::Set(_items) => Kind::Mixed,
-            DataType::Struct(_struct_fields, _struct_bracket_kind) => Kind::Mixed,
-            DataType::Union(_union_fields) => Kind::Mixed,
-            DataType::Nullable(_data_type) => Kind::Mixed,
-            DataType::LowCardinality(_data_type) => Kind::Mixed,
-            DataType::Unspecified => Kind::Mixed,
-            DataType::Trigger => Kind::Mixed,
-            DataType::AnyType => Kind::Mixed,
-            DataType::GeometricType(_geometric_type_kind) => Kind::Mixed,
+            ir::SqlType::Integer { .. } => Kind::Int,
+            ir::SqlType::Float { .. } => Kind::Float,
+            ir::SqlType::Boolean => Kind::Bool,
+            // Exact numerics stay strings so no precision is lost.
+            ir::SqlType::Decimal { .. } => Kind::String,
+            // PDO returns binary columns as byte strings.
+            ir::SqlType::Char { .. }
+            | ir::SqlType::VarChar { .. }
+            | ir::SqlType::Text
+            | ir::SqlType::Binary { .. }
+            | ir::SqlType::Interval
+            | ir::SqlType::Uuid
+            | ir::SqlType::Enum { .. } => Kind::String,
+            ir::SqlType::Date | ir::SqlType::Time | ir::SqlType::Timestamp { .. } => Kind::DateTime,
+            ir::SqlType::Json => Kind::Array,
+            ir::SqlType::Array { .. } | ir::SqlType::Other { .. } => Kind::Mixed,
         }
     }
 }
 
-impl From<DataType> for Kind {
-    fn from(value: DataType) -> Self {
-        Kind::from(&value)
-    }
-}
-
 #[derive(Debug, Error)]
 pub enum TypeParseError {
     #[error("Unknown type: {0}")]
     UnknownType(String),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn class(sql: &str) -> Class {
+        Class::new(&crate::lang::testing::table(sql))
+    }
+
+    #[test]
+    fn length_guards() {
+        let class = class(
+            "CREATE TABLE t (code CHAR(4) NOT NULL, hash VARBINARY(16), qty INT UNSIGNED NOT NULL)",
+        );
+        let guards: Vec<Vec<String>> = class.fields.iter().map(Field::guards).collect();
+
+        assert!(guards[0][0].contains("mb_strlen($this->code) > 4"));
+        assert!(guards[1][0].contains("$this->hash !== null && strlen($this->hash) > 16"));
+        assert!(guards[2][0].contains("$this->qty < 0"));
+    }
+
+    #[test]
+    fn relation_names() {
+        let table = crate::lang::testing::table(
+            "CREATE TABLE posts (
+                author_id INT REFERENCES users (id),
+                editor INT REFERENCES users (id),
+                user_id INT REFERENCES users (id),
+                category_code CHAR(2),
+                category_kind CHAR(1),
+                FOREIGN KEY (category_code, category_kind)
+                    REFERENCES billing.categories (code, kind)
+            );",
+        );
+        let keys: Vec<_> = table.foreign_keys().collect();
+
+        assert_eq!(relation_name(keys[0], &[]).unwrap(), "author");
+        assert_eq!(relation_name(keys[1], &[]).unwrap(), "user");
+        assert_eq!(relation_name(keys[1], &["user"]).unwrap(), "userByEditor");
+        assert_eq!(relation_name(keys[2], &[]).unwrap(), "user");
+        assert_eq!(relation_name(keys[3], &[]).unwrap(), "category");
+    }
+}
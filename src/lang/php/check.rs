@@ -0,0 +1,423 @@
+use sqlparser::ast::{
+    BinaryOperator, Expr, Function, FunctionArg, FunctionArgExpr, FunctionArguments, UnaryOperator,
+    Value,
+};
+
+use super::{Field, Kind, php_string};
+
+/// A `CHECK` constraint together with its PHP translation, if one exists.
+#[derive(Debug)]
+pub struct Check {
+    pub name: Option<String>,
+    /// The constraint expression as written in the schema.
+    pub sql: String,
+    /// A PHP boolean expression that holds whenever the constraint does.
+    pub condition: Option<String>,
+}
+
+impl Check {
+    pub fn new(name: Option<String>, expr: &Expr, fields: &[Field]) -> Self {
+        let mut translator = Translator {
+            fields,
+            referenced: vec![],
+        };
+
+        Self {
+            name,
+            sql: expr.to_string(),
+            condition: translator.predicate(expr, false, true),
+        }
+    }
+
+    /// The violation message reported by `validate()`.
+    pub fn message(&self) -> String {
+        match &self.name {
+            Some(name) => format!("Check constraint {name} failed: {}", self.sql),
+            None => format!("Check constraint failed: {}", self.sql),
+        }
+    }
+}
+
+struct Translator<'a> {
+    fields: &'a [Field],
+    /// The fields read since the current predicate started.
+    referenced: Vec<&'a Field>,
+}
+
+impl<'a> Translator<'a> {
+    /// Translates `expr` as a condition. SQL only rejects a row when its
+    /// check is false, and a comparison with NULL is neither true nor false,
+    /// so each comparison holds when a nullable column it reads is null, or
+    /// fails when it's `negated` by an enclosing `NOT`. `top` leaves the
+    /// condition unparenthesized.
+    fn predicate(&mut self, expr: &Expr, negated: bool, top: bool) -> Option<String> {
+        match expr {
+            Expr::BinaryOp { left, op, right }
+                if matches!(op, BinaryOperator::And | BinaryOperator::Or) =>
+            {
+                let op = match op {
+                    BinaryOperator::And => "&&",
+                    _ => "||",
+                };
+                Some(format!(
+                    "{} {op} {}",
+                    self.predicate(left, negated, false)?,
+                    self.predicate(right, negated, false)?
+                ))
+            }
+            Expr::UnaryOp {
+                op: UnaryOperator::Not,
+                expr,
+            } => {
+                // `NOT (x)` already has its parentheses.
+                let expr = match expr.as_ref() {
+                    Expr::Nested(expr) => expr,
+                    expr => expr,
+                };
+                Some(format!("!({})", self.predicate(expr, !negated, true)?))
+            }
+            Expr::Nested(expr) => Some(format!("({})", self.predicate(expr, negated, true)?)),
+            Expr::IsNull(_) | Expr::IsNotNull(_) => {
+                let condition = self.expr(expr);
+                self.referenced.clear();
+                condition
+            }
+            _ => {
+                let condition = self.expr(expr);
+                let mut nullable: Vec<&Field> = vec![];
+                for field in self.referenced.drain(..) {
+                    if field.holds_null() && !nullable.iter().any(|f| f.name == field.name) {
+                        nullable.push(field);
+                    }
+                }
+                let condition = condition?;
+                if nullable.is_empty() {
+                    return Some(condition);
+                }
+
+                let (test, join) = match negated {
+                    false => ("=== null", " || "),
+                    true => ("!== null", " && "),
+                };
+                let guarded = nullable
+                    .iter()
+                    .map(|field| format!("$this->{} {test}", field.name))
+                    .chain([condition])
+                    .collect::<Vec<_>>()
+                    .join(join);
+                match top {
+                    true => Some(guarded),
+                    false => Some(format!("({guarded})")),
+                }
+            }
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Identifier(ident) => self.column(&ident.value),
+            Expr::CompoundIdentifier(idents) => self.column(&idents.last()?.value),
+            Expr::Value(v) => match &v.value {
+                Value::Number(n, _) => Some(n.to_string()),
+                Value::SingleQuotedString(s)
+                | Value::DoubleQuotedString(s)
+                | Value::NationalStringLiteral(s)
+                | Value::EscapedStringLiteral(s) => Some(php_string(s)),
+                Value::Boolean(b) => Some(b.to_string()),
+                Value::Null => Some(String::from("null")),
+                _ => None,
+            },
+            Expr::Nested(expr) => Some(format!("({})", self.expr(expr)?)),
+            Expr::UnaryOp { op, expr } => {
+                let operand = self.expr(expr)?;
+                match op {
+                    UnaryOperator::Not => Some(format!("!({operand})")),
+                    // `--x` would be a decrement in PHP.
+                    UnaryOperator::Minus if operand.starts_with('-') => {
+                        Some(format!("-({operand})"))
+                    }
+                    UnaryOperator::Minus => Some(format!("-{operand}")),
+                    UnaryOperator::Plus => Some(operand),
+                    _ => None,
+                }
+            }
+            Expr::BinaryOp { left, op, right } => {
+                let op = match op {
+                    BinaryOperator::Gt => ">",
+                    BinaryOperator::Lt => "<",
+                    BinaryOperator::GtEq => ">=",
+                    BinaryOperator::LtEq => "<=",
+                    // Strings are compared strictly, as `"1e1" == "10"` in PHP.
+                    BinaryOperator::Eq if self.both_strings(left, right) => "===",
+                    BinaryOperator::NotEq if self.both_strings(left, right) => "!==",
+                    BinaryOperator::Eq => "==",
+                    BinaryOperator::NotEq => "!=",
+                    BinaryOperator::And => "&&",
+                    BinaryOperator::Or => "||",
+                    BinaryOperator::Plus => "+",
+                    BinaryOperator::Minus => "-",
+                    BinaryOperator::Multiply => "*",
+                    BinaryOperator::Divide => "/",
+                    BinaryOperator::Modulo => "%",
+                    _ => return None,
+                };
+                // A column is compared with literals of its own type.
+                let left_kind = self.kind_of(left);
+                let right_kind = self.kind_of(right);
+                Some(format!(
+                    "{} {op} {}",
+                    self.operand(left, right_kind)?,
+                    self.operand(right, left_kind)?
+                ))
+            }
+            Expr::Between {
+                expr,
+                negated,
+                low,
+                high,
+            } => {
+                let kind = self.kind_of(expr);
+                let value = self.expr(expr)?;
+                let range = format!(
+                    "{value} >= {} && {value} <= {}",
+                    self.operand(low, kind)?,
+                    self.operand(high, kind)?
+                );
+                match negated {
+                    true => Some(format!("!({range})")),
+                    false => Some(format!("({range})")),
+                }
+            }
+            Expr::InList {
+                expr,
+                list,
+                negated,
+            } => {
+                let kind = self.kind_of(expr);
+                let numeric = self.is_numeric(expr);
+                let value = self.expr(expr)?;
+                let items = list
+                    .iter()
+                    .map(|item| self.operand(item, kind))
+                    .collect::<Option<Vec<_>>>()?
+                    .join(", ");
+                let not = if *negated { "!" } else { "" };
+                // Numbers are compared by value, so `1.0` matches `1`. Strings
+                // are compared strictly, as `"1e1" == "10"` in PHP.
+                let strict = !numeric && list.iter().all(is_string_literal);
+                match strict {
+                    true => Some(format!("{not}in_array({value}, [{items}], true)")),
+                    false => Some(format!("{not}in_array({value}, [{items}])")),
+                }
+            }
+            Expr::IsNull(expr) => Some(format!("{} === null", self.expr(expr)?)),
+            Expr::IsNotNull(expr) => Some(format!("{} !== null", self.expr(expr)?)),
+            Expr::Function(function) => self.function(function),
+            _ => None,
+        }
+    }
+
+    /// The field `expr` reads, if it is a column.
+    fn field(&self, expr: &Expr) -> Option<&'a Field> {
+        let name = match expr {
+            Expr::Identifier(ident) => &ident.value,
+            Expr::CompoundIdentifier(idents) => &idents.last()?.value,
+            Expr::Nested(expr) => return self.field(expr),
+            _ => return None,
+        };
+
+        self.fields.iter().find(|field| &field.name == name)
+    }
+
+    fn kind_of(&self, expr: &Expr) -> Option<Kind> {
+        self.field(expr).map(|field| field.type_annotation)
+    }
+
+    /// Whether `left` and `right` are both strings, as literals or text
+    /// columns.
+    fn both_strings(&self, left: &Expr, right: &Expr) -> bool {
+        let is_string = |expr: &Expr| {
+            is_string_literal(expr)
+                || self.field(expr).is_some_and(|field| {
+                    !field.exact_numeric && matches!(field.type_annotation, Kind::String)
+                })
+        };
+        is_string(left) && is_string(right)
+    }
+
+    fn is_numeric(&self, expr: &Expr) -> bool {
+        self.field(expr).is_some_and(|field| {
+            field.exact_numeric || matches!(field.type_annotation, Kind::Int | Kind::Float)
+        })
+    }
+
+    /// Translates `expr` as compared with a value of `kind`. String literals
+    /// compared with a `DateTime` become one.
+    fn operand(&mut self, expr: &Expr, kind: Option<Kind>) -> Option<String> {
+        match (kind, expr) {
+            (Some(Kind::DateTime), expr) if is_string_literal(expr) => {
+                Some(format!("new DateTime({})", self.expr(expr)?))
+            }
+            _ => self.expr(expr),
+        }
+    }
+
+    fn column(&mut self, name: &str) -> Option<String> {
+        let field = self.fields.iter().find(|field| field.name == name)?;
+        self.referenced.push(field);
+
+        Some(format!("$this->{}", field.name))
+    }
+
+    /// Translates the string length functions; anything else is left alone.
+    fn function(&mut self, function: &Function) -> Option<String> {
+        let php = match function.name.to_string().to_lowercase().as_str() {
+            "length" | "char_length" | "character_length" | "len" => "mb_strlen",
+            "octet_length" => "strlen",
+            _ => return None,
+        };
+
+        let FunctionArguments::List(list) = &function.args else {
+            return None;
+        };
+        let [FunctionArg::Unnamed(FunctionArgExpr::Expr(arg))] = list.args.as_slice() else {
+            return None;
+        };
+
+        Some(format!("{php}({})", self.expr(arg)?))
+    }
+}
+
+fn is_string_literal(expr: &Expr) -> bool {
+    matches!(
+        expr,
+        Expr::Value(v) if matches!(
+            v.value,
+            Value::SingleQuotedString(_)
+                | Value::DoubleQuotedString(_)
+                | Value::NationalStringLiteral(_)
+                | Value::EscapedStringLiteral(_)
+        )
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lang::php::Class;
+    use crate::lang::testing::table;
+
+    /// The PHP conditions of the checks on `sql`'s table.
+    fn conditions(sql: &str) -> Vec<Option<String>> {
+        Class::new(&table(sql))
+            .checks
+            .into_iter()
+            .map(|check| check.condition)
+            .collect()
+    }
+
+    #[test]
+    fn comparisons_and_nulls() {
+        assert_eq!(
+            conditions(
+                "CREATE TABLE t (
+                    a INT NOT NULL,
+                    b INT,
+                    CHECK (a > 0 AND a <= b),
+                    CHECK (b IS NOT NULL),
+                    CHECK (a > 0 AND b > 0),
+                    CHECK (NOT (b > 0) OR a = 1)
+                )"
+            ),
+            [
+                Some(String::from(
+                    "$this->a > 0 && ($this->b === null || $this->a <= $this->b)"
+                )),
+                Some(String::from("$this->b !== null")),
+                Some(String::from(
+                    "$this->a > 0 && ($this->b === null || $this->b > 0)"
+                )),
+                Some(String::from(
+                    "!($this->b !== null && $this->b > 0) || $this->a == 1"
+                )),
+            ]
+        );
+    }
+
+    #[test]
+    fn strings_compare_strictly() {
+        assert_eq!(
+            conditions(
+                "CREATE TABLE t (
+                    code TEXT NOT NULL,
+                    other TEXT NOT NULL,
+                    n INT NOT NULL,
+                    CHECK (code = '10'),
+                    CHECK (code <> other),
+                    CHECK (n = 10)
+                )"
+            ),
+            [
+                Some(String::from("$this->code === '10'")),
+                Some(String::from("$this->code !== $this->other")),
+                Some(String::from("$this->n == 10")),
+            ]
+        );
+    }
+
+    #[test]
+    fn negated_negatives() {
+        assert_eq!(
+            conditions("CREATE TABLE t (a INT NOT NULL, CHECK (a > - -1), CHECK (a < -a))"),
+            [
+                Some(String::from("$this->a > -(-1)")),
+                Some(String::from("$this->a < -$this->a")),
+            ]
+        );
+    }
+
+    #[test]
+    fn in_lists() {
+        assert_eq!(
+            conditions(
+                "CREATE TABLE t (
+                    price DECIMAL(5, 2) NOT NULL CHECK (price IN (1, 2)),
+                    ratio FLOAT NOT NULL CHECK (ratio NOT IN (0.5)),
+                    size TEXT NOT NULL CHECK (size IN ('s', 'm'))
+                )"
+            ),
+            [
+                Some(String::from("in_array($this->price, [1, 2])")),
+                Some(String::from("!in_array($this->ratio, [0.5])")),
+                Some(String::from("in_array($this->size, ['s', 'm'], true)")),
+            ]
+        );
+    }
+
+    #[test]
+    fn dates_compare_as_datetimes() {
+        assert_eq!(
+            conditions(
+                "CREATE TABLE t (
+                    starts DATE NOT NULL,
+                    ends TIMESTAMP NOT NULL,
+                    CHECK (starts >= '2000-01-01'),
+                    CHECK (ends BETWEEN '2000-01-01' AND '2100-01-01')
+                )"
+            ),
+            [
+                Some(String::from("$this->starts >= new DateTime('2000-01-01')")),
+                Some(String::from(
+                    "($this->ends >= new DateTime('2000-01-01') && $this->ends <= new DateTime('2100-01-01'))"
+                )),
+            ]
+        );
+    }
+
+    #[test]
+    fn untranslatable() {
+        assert_eq!(
+            conditions("CREATE TABLE t (a TEXT NOT NULL, CHECK (a ~ '^x'))"),
+            [None]
+        );
+    }
+}
@@ -0,0 +1,476 @@
+use std::fmt::Display;
+
+use super::{DefaultValue, Kind, backed_enums, enum_case, enum_name, php_string, relation_name};
+use crate::lang::{CamelCase, CommentStyle, PascalCase, SnakeCase};
+use crate::sql::ir::{Column, Constraint, ForeignKey, SqlType, Table};
+
+/// What a property of the entity maps to.
+enum Mapping<'a> {
+    Column(&'a Column),
+    /// A foreign key, as a reference to the related entity.
+    ManyToOne(&'a ForeignKey),
+}
+
+struct Property<'a> {
+    name: String,
+    mapping: Mapping<'a>,
+    /// The PHP type, without nullability.
+    type_name: String,
+    nullable: bool,
+    /// The database assigns the value, so there is no setter.
+    generated: bool,
+}
+
+/// A Doctrine ORM entity for a table, mapped with PHP attributes.
+pub struct Doctrine<'a> {
+    table: &'a Table,
+    class_name: &'a str,
+    properties: Vec<Property<'a>>,
+}
+
+impl<'a> Doctrine<'a> {
+    pub const IMPORTS: &'static [&'static str] = &[
+        "Doctrine\\DBAL\\Types\\Types",
+        "Doctrine\\ORM\\Mapping as ORM",
+    ];
+
+    /// `related` names the entity of a referenced table, given its qualified
+    /// name, as referenced from this entity's namespace.
+    pub fn new(
+        table: &'a Table,
+        class_name: &'a str,
+        related: impl Fn(&[String]) -> anyhow::Result<String>,
+    ) -> anyhow::Result<Self> {
+        let primary_key = table.primary_key();
+
+        // Each foreign key replaces its columns with one reference. Keys
+        // overlapping the primary key, or an earlier key, stay plain columns.
+        let mut keys: Vec<&ForeignKey> = vec![];
+        for key in table.foreign_keys() {
+            let overlaps = |columns: &[String]| key.columns.iter().any(|c| columns.contains(c));
+            if key.columns.is_empty()
+                || key
+                    .columns
+                    .iter()
+                    .any(|c| primary_key.contains(&c.as_str()))
+                || keys.iter().any(|other| overlaps(&other.columns))
+            {
+                continue;
+            }
+            keys.push(key);
+        }
+        let mapped_by_key =
+            |column: &Column| keys.iter().any(|key| key.columns.contains(&column.name));
+
+        let mut properties = vec![];
+        for column in &table.columns {
+            if let Some(key) = keys.iter().find(|key| key.columns[0] == column.name) {
+                properties.push(Property {
+                    // Named below, once every column has its name.
+                    name: String::new(),
+                    type_name: related(&key.foreign_table)?,
+                    nullable: key.columns.iter().any(|name| {
+                        table
+                            .columns
+                            .iter()
+                            .any(|column| &column.name == name && column.nullable)
+                    }),
+                    generated: false,
+                    mapping: Mapping::ManyToOne(key),
+                });
+            } else if !mapped_by_key(column) {
+                properties.push(Property {
+                    name: property_name(&column.name),
+                    type_name: type_of(column, class_name),
+                    nullable: column.nullable || column.generated,
+                    generated: column.generated,
+                    mapping: Mapping::Column(column),
+                });
+            }
+        }
+
+        for i in 0..properties.len() {
+            let Mapping::ManyToOne(key) = &properties[i].mapping else {
+                continue;
+            };
+            let taken: Vec<&str> = properties.iter().map(|p| p.name.as_str()).collect();
+            properties[i].name = relation_name(key, &taken).unwrap_or_default();
+        }
+
+        Ok(Self {
+            table,
+            class_name,
+            properties,
+        })
+    }
+
+    /// The entity-level attributes: `#[ORM\Entity]`, `#[ORM\Table]` and its
+    /// indexes.
+    fn class_attributes(&self) -> String {
+        let mut table = format!("name: {}", php_string(&self.table.name));
+        if !self.table.schema.is_empty() {
+            table.push_str(&format!(
+                ", schema: {}",
+                php_string(&self.table.schema.join("."))
+            ));
+        }
+        let mut attributes = format!("#[ORM\\Entity]\n#[ORM\\Table({table})]\n");
+
+        let index = |kind: &str, name: &Option<String>, columns: &[String]| {
+            let name = match name {
+                Some(name) => format!("name: {}, ", php_string(name)),
+                None => String::new(),
+            };
+            let columns: Vec<String> = columns.iter().map(|c| php_string(c)).collect();
+            format!("#[ORM\\{kind}({name}columns: [{}])]\n", columns.join(", "))
+        };
+        for constraint in &self.table.constraints {
+            // Single columns are marked `unique` on the column instead.
+            if let Constraint::Unique { name, columns } = constraint
+                && columns.len() > 1
+            {
+                attributes.push_str(&index("UniqueConstraint", name, columns));
+            }
+        }
+        for table_index in &self.table.indexes {
+            let kind = match table_index.unique {
+                true => "UniqueConstraint",
+                false => "Index",
+            };
+            attributes.push_str(&index(kind, &table_index.name, &table_index.columns));
+        }
+
+        attributes
+    }
+
+    /// The attributes mapping `property`, each on its own line.
+    fn property_attributes(&self, property: &Property) -> Vec<String> {
+        match &property.mapping {
+            Mapping::Column(column) => {
+                let primary_key = self.table.primary_key();
+                let mut attributes = vec![];
+                if primary_key.contains(&column.name.as_str()) {
+                    attributes.push(String::from("#[ORM\\Id]"));
+                    if column.generated && primary_key.len() == 1 {
+                        attributes.push(String::from("#[ORM\\GeneratedValue]"));
+                    }
+                }
+                attributes.push(self.column_attribute(column, &property.name));
+
+                attributes
+            }
+            Mapping::ManyToOne(key) => {
+                let mut attributes = vec![format!(
+                    "#[ORM\\ManyToOne(targetEntity: {}::class)]",
+                    property.type_name
+                )];
+                for (i, name) in key.columns.iter().enumerate() {
+                    let mut args = vec![format!("name: {}", php_string(name))];
+                    if let Some(referenced) = key.referred_columns.get(i) {
+                        args.push(format!("referencedColumnName: {}", php_string(referenced)));
+                    }
+                    if !self
+                        .table
+                        .columns
+                        .iter()
+                        .any(|column| &column.name == name && column.nullable)
+                    {
+                        args.push(String::from("nullable: false"));
+                    }
+                    if let Some(action) = &key.on_delete {
+                        args.push(format!("onDelete: {}", php_string(action)));
+                    }
+                    attributes.push(format!("#[ORM\\JoinColumn({})]", args.join(", ")));
+                }
+
+                attributes
+            }
+        }
+    }
+
+    fn column_attribute(&self, column: &Column, property: &str) -> String {
+        let mut args = vec![];
+        if property != column.name {
+            args.push(format!("name: {}", php_string(&column.name)));
+        }
+        args.push(format!("type: Types::{}", type_constant(&column.sql_type)));
+
+        let mut options = vec![];
+        match &column.sql_type {
+            SqlType::Char { length } => {
+                if let Some(length) = length {
+                    args.push(format!("length: {}", length.value));
+                }
+                options.push(String::from("'fixed' => true"));
+            }
+            SqlType::VarChar {
+                length: Some(length),
+            } => args.push(format!("length: {}", length.value)),
+            SqlType::Binary {
+                length: Some(length),
+            } => args.push(format!("length: {length}")),
+            SqlType::Decimal { precision, scale } => {
+                if let Some(precision) = precision {
+                    args.push(format!("precision: {precision}"));
+                }
+                if let Some(scale) = scale {
+                    args.push(format!("scale: {scale}"));
+                }
+            }
+            SqlType::Integer { unsigned: true, .. } => {
+                options.push(String::from("'unsigned' => true"))
+            }
+            SqlType::Enum { .. } => args.push(format!(
+                "enumType: {}::class",
+                enum_name(self.class_name, column)
+            )),
+            SqlType::Array { .. } | SqlType::Other { .. } => args.push(format!(
+                "columnDefinition: {}",
+                php_string(&column.data_type.to_string())
+            )),
+            _ => {}
+        }
+
+        if let Some(default) = default_of(column) {
+            options.push(format!("'default' => {default}"));
+        }
+
+        if column.nullable {
+            args.push(String::from("nullable: true"));
+        }
+        if self.table.is_unique(&column.name) {
+            args.push(String::from("unique: true"));
+        }
+        if !options.is_empty() {
+            args.push(format!("options: [{}]", options.join(", ")));
+        }
+        let primary_key = self.table.primary_key();
+        if column.generated && !primary_key.contains(&column.name.as_str()) {
+            args.push(String::from("insertable: false, updatable: false"));
+        }
+
+        format!("#[ORM\\Column({})]", args.join(", "))
+    }
+
+    fn accessors(&self, property: &Property) -> String {
+        let type_name = nullable(property);
+        let mut accessors = format!(
+            "\n    public function get{}(): {type_name} {{\n        return $this->{};\n    }}\n",
+            property.name.to_snake_case().to_pascal_case(),
+            property.name
+        );
+        if !property.generated {
+            accessors.push_str(&format!(
+                "\n    public function set{}({type_name} ${1}): static {{\n        $this->{1} = ${1};\n\n        return $this;\n    }}\n",
+                property.name.to_snake_case().to_pascal_case(),
+                property.name
+            ));
+        }
+
+        accessors
+    }
+}
+
+/// The property for `column`, e.g. `createdAt` for `created_at`.
+fn property_name(column: &str) -> String {
+    let name = column.to_snake_case().to_camel_case();
+    match name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        true => name,
+        false => format!("column{}", name.to_pascal_case()),
+    }
+}
+
+/// The PHP type of `column`'s property, without nullability.
+fn type_of(column: &Column, class_name: &str) -> String {
+    let name = match &column.sql_type {
+        SqlType::Integer { bytes, .. } if *bytes <= 8 => "int",
+        SqlType::Float { .. } => "float",
+        SqlType::Boolean => "bool",
+        SqlType::Date | SqlType::Time | SqlType::Timestamp { .. } => "\\DateTimeImmutable",
+        SqlType::Interval => "\\DateInterval",
+        SqlType::Json => "array",
+        SqlType::Binary { length: None } => "mixed",
+        SqlType::Enum { .. } => return enum_name(class_name, column),
+        SqlType::Integer { .. }
+        | SqlType::Decimal { .. }
+        | SqlType::Char { .. }
+        | SqlType::VarChar { .. }
+        | SqlType::Text
+        | SqlType::Binary { .. }
+        | SqlType::Uuid
+        | SqlType::Array { .. }
+        | SqlType::Other { .. } => "string",
+    };
+
+    name.to_string()
+}
+
+/// The PHP literal for `column`'s `DEFAULT`, as passed in the column's
+/// options. `None` when the database evaluates it, e.g. `CURRENT_TIMESTAMP`.
+fn default_of(column: &Column) -> Option<String> {
+    let kind = match &column.sql_type {
+        SqlType::Integer { bytes, .. } if *bytes <= 8 => Kind::Int,
+        SqlType::Float { .. } => Kind::Float,
+        SqlType::Boolean => Kind::Bool,
+        SqlType::Integer { .. }
+        | SqlType::Decimal { .. }
+        | SqlType::Char { .. }
+        | SqlType::VarChar { .. }
+        | SqlType::Text
+        | SqlType::Uuid
+        | SqlType::Enum { .. } => Kind::String,
+        _ => return None,
+    };
+
+    match DefaultValue::new(column.default.as_ref()?, &kind) {
+        DefaultValue::Literal(literal) => Some(literal),
+        DefaultValue::Null | DefaultValue::Generated => None,
+    }
+}
+
+/// The initial value of `property`: its column's default, else `null` for
+/// values unknown until the entity is flushed.
+fn initializer(property: &Property, class_name: &str) -> Option<String> {
+    if let Mapping::Column(column) = property.mapping
+        && !column.generated
+        && let Some(default) = default_of(column)
+    {
+        let SqlType::Enum { values } = &column.sql_type else {
+            return Some(default);
+        };
+        if let Some(value) = values.iter().find(|value| php_string(value) == default) {
+            return Some(format!(
+                "{}::{}",
+                enum_name(class_name, column),
+                enum_case(value)
+            ));
+        }
+    }
+
+    property.nullable.then(|| String::from("null"))
+}
+
+/// The `Types` constant of the DBAL type `sql_type` is mapped with.
+fn type_constant(sql_type: &SqlType) -> &'static str {
+    match sql_type {
+        SqlType::Integer { bytes: 1 | 2, .. } => "SMALLINT",
+        SqlType::Integer { bytes: 4, .. } => "INTEGER",
+        SqlType::Integer { bytes: 8, .. } => "BIGINT",
+        // Wider than any PHP integer.
+        SqlType::Integer { .. } | SqlType::Decimal { .. } => "DECIMAL",
+        SqlType::Float { .. } => "FLOAT",
+        SqlType::Boolean => "BOOLEAN",
+        SqlType::Text => "TEXT",
+        SqlType::Binary { length: Some(_) } => "BINARY",
+        SqlType::Binary { length: None } => "BLOB",
+        SqlType::Date => "DATE_IMMUTABLE",
+        SqlType::Time => "TIME_IMMUTABLE",
+        SqlType::Timestamp { time_zone: true } => "DATETIMETZ_IMMUTABLE",
+        SqlType::Timestamp { time_zone: false } => "DATETIME_IMMUTABLE",
+        SqlType::Interval => "DATEINTERVAL",
+        SqlType::Json => "JSON",
+        SqlType::Uuid => "GUID",
+        SqlType::Char { .. }
+        | SqlType::VarChar { .. }
+        | SqlType::Enum { .. }
+        | SqlType::Array { .. }
+        | SqlType::Other { .. } => "STRING",
+    }
+}
+
+/// The declared type of `property`, e.g. `?string`.
+fn nullable(property: &Property) -> String {
+    match property.nullable && property.type_name != "mixed" {
+        true => format!("?{}", property.type_name),
+        false => property.type_name.clone(),
+    }
+}
+
+impl Display for Doctrine<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut properties = String::new();
+        let mut accessors = String::new();
+        for (i, property) in self.properties.iter().enumerate() {
+            if i > 0 {
+                properties.push('\n');
+            }
+            if let Mapping::Column(column) = property.mapping
+                && let Some(comment) = &column.comment
+            {
+                properties.push_str(&CommentStyle::BLOCK.render(comment.lines(), "    "));
+            }
+            for attribute in self.property_attributes(property) {
+                properties.push_str(&format!("    {attribute}\n"));
+            }
+            let default = match initializer(property, self.class_name) {
+                Some(value) => format!(" = {value}"),
+                None => String::new(),
+            };
+            properties.push_str(&format!(
+                "    private {} ${}{default};\n",
+                nullable(property),
+                property.name
+            ));
+
+            accessors.push_str(&self.accessors(property));
+        }
+
+        let doc = match &self.table.comment {
+            Some(comment) => CommentStyle::BLOCK.render(comment.lines(), ""),
+            None => String::new(),
+        };
+
+        write!(
+            f,
+            "{}{doc}{}class {} {{\n{properties}{accessors}}}",
+            backed_enums(self.table, self.class_name),
+            self.class_attributes(),
+            self.class_name
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lang::testing::render;
+
+    const DOCTRINE: &str = "[php]\nflavor = \"doctrine\"";
+
+    #[test]
+    fn defaults() {
+        let code = render(
+            DOCTRINE,
+            "CREATE TABLE t (
+                views INT NOT NULL DEFAULT 0,
+                status ENUM('draft', 'live') NOT NULL DEFAULT 'live',
+                note TEXT DEFAULT 'none',
+                created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+            )",
+        );
+
+        assert!(code.contains(
+            "    #[ORM\\Column(type: Types::INTEGER, options: ['default' => 0])]\n    private int $views = 0;\n"
+        ));
+        assert!(code.contains(
+            "options: ['default' => 'live'])]\n    private TStatus $status = TStatus::Live;\n"
+        ));
+        assert!(code.contains("    private ?string $note = 'none';\n"));
+        assert!(code.contains(
+            "    #[ORM\\Column(name: 'created_at', type: Types::DATETIME_IMMUTABLE)]\n    private \\DateTimeImmutable $createdAt;\n"
+        ));
+    }
+
+    #[test]
+    fn generated_keys_are_nullable() {
+        let code = render(
+            DOCTRINE,
+            "CREATE TABLE t (id BIGINT AUTO_INCREMENT PRIMARY KEY, n INT NOT NULL)",
+        );
+
+        assert!(code.contains(
+            "    #[ORM\\Id]\n    #[ORM\\GeneratedValue]\n    #[ORM\\Column(type: Types::BIGINT)]\n    private ?int $id = null;\n"
+        ));
+        assert!(!code.contains("public function setId("));
+        assert!(code.contains("    private int $n;\n"));
+    }
+}
@@ -0,0 +1,326 @@
+use std::fmt::Display;
+
+use super::{backed_enums, enum_name, php_string, relation_name};
+use crate::lang::CommentStyle;
+use crate::sql::ir::{Column, SqlType, Table};
+
+/// A `belongsTo` relationship from a single-column foreign key.
+struct Relation {
+    method: String,
+    /// The related model as referenced from this one.
+    class: String,
+    foreign_key: String,
+    owner_key: Option<String>,
+}
+
+/// A Laravel Eloquent model for a table.
+pub struct Eloquent<'a> {
+    table: &'a Table,
+    class_name: &'a str,
+    relations: Vec<Relation>,
+    /// `created_at` and `updated_at` are both present, so Eloquent can
+    /// maintain them.
+    timestamps: bool,
+}
+
+impl<'a> Eloquent<'a> {
+    /// `related` names the model of a referenced table, given its qualified
+    /// name, as referenced from this model's namespace.
+    pub fn new(
+        table: &'a Table,
+        class_name: &'a str,
+        related: impl Fn(&[String]) -> anyhow::Result<String>,
+    ) -> anyhow::Result<Self> {
+        let column_names: Vec<&str> = table.columns.iter().map(|c| c.name.as_str()).collect();
+
+        let mut relations: Vec<Relation> = vec![];
+        // Eloquent has no composite keys, so only single-column foreign keys
+        // become relationships.
+        for key in table.foreign_keys().filter(|key| key.columns.len() == 1) {
+            // A method named after a column would shadow its attribute.
+            let taken: Vec<&str> = column_names
+                .iter()
+                .copied()
+                .chain(relations.iter().map(|relation| relation.method.as_str()))
+                .collect();
+            let Some(method) = relation_name(key, &taken) else {
+                continue;
+            };
+
+            relations.push(Relation {
+                method,
+                class: related(&key.foreign_table)?,
+                foreign_key: key.columns[0].clone(),
+                owner_key: key.referred_columns.first().cloned(),
+            });
+        }
+
+        Ok(Self {
+            table,
+            class_name,
+            relations,
+            timestamps: column_names.contains(&"created_at")
+                && column_names.contains(&"updated_at"),
+        })
+    }
+
+    /// The classes the model file of `table` imports.
+    pub fn imports(table: &Table) -> Vec<&'static str> {
+        let mut imports = vec!["Illuminate\\Database\\Eloquent\\Model"];
+        if table.foreign_keys().any(|key| key.columns.len() == 1) {
+            imports.push("Illuminate\\Database\\Eloquent\\Relations\\BelongsTo");
+        }
+
+        imports
+    }
+
+    fn is_timestamp(&self, column: &Column) -> bool {
+        self.timestamps && matches!(column.name.as_str(), "created_at" | "updated_at")
+    }
+
+    /// The single primary key column, if it is incremented by the database.
+    fn incrementing(&self) -> bool {
+        match self.table.primary_key().as_slice() {
+            [key] => self.table.columns.iter().any(|column| {
+                &column.name == key
+                    && column.generated
+                    && matches!(column.sql_type, SqlType::Integer { .. })
+            }),
+            _ => false,
+        }
+    }
+
+    /// The `$casts` entry for `column`, if Eloquent should convert it.
+    fn cast(&self, column: &Column) -> Option<String> {
+        if self.is_timestamp(column) {
+            // Eloquent already casts its own timestamps.
+            return None;
+        }
+
+        match &column.sql_type {
+            SqlType::Boolean => Some(php_string("boolean")),
+            SqlType::Date => Some(php_string("date")),
+            SqlType::Timestamp { .. } => Some(php_string("datetime")),
+            SqlType::Json => Some(php_string("array")),
+            SqlType::Decimal {
+                scale: Some(scale), ..
+            } => Some(php_string(&format!("decimal:{scale}"))),
+            SqlType::Enum { .. } => Some(format!("{}::class", enum_name(self.class_name, column))),
+            _ => None,
+        }
+    }
+
+    /// The type of `column`'s attribute once cast, for the `@property` tags.
+    fn property_type(&self, column: &Column) -> String {
+        let name = match &column.sql_type {
+            SqlType::Integer { .. } => "int",
+            SqlType::Float { .. } => "float",
+            SqlType::Boolean => "bool",
+            SqlType::Date | SqlType::Timestamp { .. } => "\\Illuminate\\Support\\Carbon",
+            SqlType::Json => "array",
+            SqlType::Enum { .. } => {
+                return self.nullable(column, enum_name(self.class_name, column));
+            }
+            SqlType::Other { .. } => return String::from("mixed"),
+            SqlType::Decimal { .. }
+            | SqlType::Char { .. }
+            | SqlType::VarChar { .. }
+            | SqlType::Text
+            | SqlType::Binary { .. }
+            | SqlType::Time
+            | SqlType::Interval
+            | SqlType::Uuid
+            | SqlType::Array { .. } => "string",
+        };
+
+        self.nullable(column, name.to_string())
+    }
+
+    fn nullable(&self, column: &Column, type_name: String) -> String {
+        match column.nullable {
+            true => format!("?{type_name}"),
+            false => type_name,
+        }
+    }
+
+    fn doc(&self) -> String {
+        let mut lines: Vec<String> = match &self.table.comment {
+            Some(comment) => comment
+                .lines()
+                .map(String::from)
+                .chain([String::new()])
+                .collect(),
+            None => vec![],
+        };
+        for column in &self.table.columns {
+            let tag = match column.generated {
+                true => "@property-read",
+                false => "@property",
+            };
+            lines.push(format!(
+                "{tag} {} ${}",
+                self.property_type(column),
+                column.name
+            ));
+        }
+
+        match lines.is_empty() {
+            true => String::new(),
+            false => CommentStyle::BLOCK.render(lines.iter().map(String::as_str), ""),
+        }
+    }
+
+    /// The `$table`, `$primaryKey`, `$incrementing`, `$keyType` and
+    /// `$timestamps` properties.
+    fn table_properties(&self) -> String {
+        let mut table = self.table.schema.clone();
+        table.push(self.table.name.clone());
+        let mut properties = format!("    protected $table = {};\n", php_string(&table.join(".")));
+
+        match self.table.primary_key().as_slice() {
+            [key] => {
+                properties.push_str(&format!(
+                    "    protected $primaryKey = {};\n",
+                    php_string(key)
+                ));
+                if !self.incrementing() {
+                    properties.push_str("    public $incrementing = false;\n");
+                }
+                let integer = self.table.columns.iter().any(|column| {
+                    &column.name == key && matches!(column.sql_type, SqlType::Integer { .. })
+                });
+                if !integer {
+                    properties.push_str("    protected $keyType = 'string';\n");
+                }
+            }
+            key => {
+                if !key.is_empty() {
+                    properties.push_str("    // Eloquent has no composite primary keys.\n");
+                }
+                properties.push_str("    protected $primaryKey = null;\n");
+                properties.push_str("    public $incrementing = false;\n");
+            }
+        }
+
+        properties.push_str(&format!("    public $timestamps = {};\n", self.timestamps));
+
+        properties
+    }
+
+    /// The columns callers may mass assign: everything the database or
+    /// Eloquent doesn't fill in itself.
+    fn fillable(&self) -> String {
+        let incrementing = self.incrementing();
+        let primary_key = self.table.primary_key();
+        let columns: String = self
+            .table
+            .columns
+            .iter()
+            .filter(|column| !column.generated && !self.is_timestamp(column))
+            .filter(|column| !(incrementing && primary_key.contains(&column.name.as_str())))
+            .map(|column| format!("        {},\n", php_string(&column.name)))
+            .collect();
+
+        format!("    /** @var list<string> */\n    protected $fillable = [\n{columns}    ];\n")
+    }
+
+    fn casts(&self) -> String {
+        let casts: String = self
+            .table
+            .columns
+            .iter()
+            .filter_map(|column| {
+                self.cast(column)
+                    .map(|cast| format!("        {} => {cast},\n", php_string(&column.name)))
+            })
+            .collect();
+
+        match casts.is_empty() {
+            true => String::new(),
+            false => format!(
+                "\n    /** @var array<string, string> */\n    protected $casts = [\n{casts}    ];\n"
+            ),
+        }
+    }
+
+    fn relations(&self) -> String {
+        self.relations
+            .iter()
+            .map(|relation| {
+                let owner_key = match &relation.owner_key {
+                    Some(key) => format!(", {}", php_string(key)),
+                    None => String::new(),
+                };
+                format!(
+                    "\n    public function {}(): BelongsTo {{\n        return $this->belongsTo({}::class, {}{owner_key});\n    }}\n",
+                    relation.method,
+                    relation.class,
+                    php_string(&relation.foreign_key)
+                )
+            })
+            .collect()
+    }
+}
+
+impl Display for Eloquent<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{}class {} extends Model {{\n{}\n{}{}\n    /** @param array<string, mixed> $row */\n    public static function fromRow(array $row): self {{\n        return (new self())->newFromBuilder($row);\n    }}\n{}}}",
+            backed_enums(self.table, self.class_name),
+            self.doc(),
+            self.class_name,
+            self.table_properties(),
+            self.fillable(),
+            self.casts(),
+            self.relations()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lang::testing::render;
+
+    const ELOQUENT: &str = "[php]\nflavor = \"eloquent\"";
+
+    #[test]
+    fn primary_keys() {
+        let code = render(ELOQUENT, "CREATE TABLE t (code CHAR(2) PRIMARY KEY)");
+        assert!(code.contains(
+            "    protected $primaryKey = 'code';\n    public $incrementing = false;\n    protected $keyType = 'string';\n"
+        ));
+
+        let code = render(
+            ELOQUENT,
+            "CREATE TABLE t (a INT, b INT, PRIMARY KEY (a, b))",
+        );
+        assert!(code.contains(
+            "    // Eloquent has no composite primary keys.\n    protected $primaryKey = null;\n"
+        ));
+    }
+
+    #[test]
+    fn timestamps_need_both_columns() {
+        let code = render(
+            ELOQUENT,
+            "CREATE TABLE t (created_at TIMESTAMP, updated_at TIMESTAMP)",
+        );
+        assert!(code.contains("    public $timestamps = true;\n"));
+        assert!(!code.contains("'created_at' => "));
+
+        let code = render(ELOQUENT, "CREATE TABLE t (created_at TIMESTAMP)");
+        assert!(code.contains("    public $timestamps = false;\n"));
+        assert!(code.contains("        'created_at' => 'datetime',\n"));
+    }
+
+    #[test]
+    fn relations_dont_shadow_columns() {
+        let code = render(
+            ELOQUENT,
+            "CREATE TABLE t (user INT REFERENCES users (id), owner_id INT REFERENCES users (id))",
+        );
+        assert!(code.contains("    public function userByUser(): BelongsTo {\n"));
+        assert!(code.contains("    public function owner(): BelongsTo {\n"));
+    }
+}
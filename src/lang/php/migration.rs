@@ -0,0 +1,860 @@
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use sqlparser::ast::{self, DataType, Expr, UnaryOperator};
+
+use super::php_string;
+use crate::lang::{PascalCase, SnakeCase};
+use crate::sql::dialect::Dialect;
+use crate::sql::ir::{Column, Constraint, ForeignKey, Index, SqlType, Table};
+use crate::sql::migrate::{self, Change};
+
+#[derive(Debug, Clone, Copy)]
+pub enum Framework {
+    /// `Schema::create` with a `Blueprint`.
+    Laravel,
+    /// A Phinx `AbstractMigration` using its table builder.
+    Phinx,
+}
+
+impl FromStr for Framework {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "laravel" => Ok(Self::Laravel),
+            "phinx" => Ok(Self::Phinx),
+            _ => Err(anyhow!("unknown migration framework: {s}")),
+        }
+    }
+}
+
+impl Framework {
+    /// Where the framework looks for migrations by default.
+    pub fn default_dir(&self) -> &'static str {
+        match self {
+            Framework::Laravel => "database/migrations",
+            Framework::Phinx => "db/migrations",
+        }
+    }
+}
+
+/// A column default as the schema builders take it.
+enum ColumnDefault {
+    /// A PHP literal.
+    Literal(String),
+    CurrentTimestamp,
+    /// Any other SQL expression, passed through as is.
+    Expression(String),
+}
+
+impl ColumnDefault {
+    fn new(expr: &Expr, sql_type: &SqlType) -> Option<Self> {
+        let (value, sign) = match expr {
+            Expr::Value(v) => (&v.value, ""),
+            Expr::UnaryOp {
+                op: UnaryOperator::Minus,
+                expr: operand,
+            } => match operand.as_ref() {
+                Expr::Value(v) => (&v.value, "-"),
+                _ => return Some(Self::Expression(expr.to_string())),
+            },
+            Expr::Nested(expr) => return Self::new(expr, sql_type),
+            _ => {
+                let sql = expr.to_string();
+                return Some(match sql.to_uppercase().as_str() {
+                    "CURRENT_TIMESTAMP" | "CURRENT_TIMESTAMP()" | "NOW()" => Self::CurrentTimestamp,
+                    _ => Self::Expression(sql),
+                });
+            }
+        };
+
+        let literal = match (value, sql_type) {
+            (ast::Value::Null, _) => return None,
+            (ast::Value::Boolean(b), _) => b.to_string(),
+            (ast::Value::Number(n, _), SqlType::Boolean) if n == "0" || n == "1" => {
+                (n == "1").to_string()
+            }
+            (ast::Value::Number(n, _), _) => format!("{sign}{n}"),
+            (
+                ast::Value::SingleQuotedString(s)
+                | ast::Value::DoubleQuotedString(s)
+                | ast::Value::NationalStringLiteral(s)
+                | ast::Value::EscapedStringLiteral(s),
+                _,
+            ) => php_string(s),
+            _ => return Some(Self::Expression(expr.to_string())),
+        };
+
+        Some(Self::Literal(literal))
+    }
+}
+
+/// A migration creating one table from `sql/`, in the schema builder of a
+/// PHP framework.
+pub struct Migration {
+    table: Table,
+    framework: Framework,
+    /// The database the SQL for what the builder can't express is written
+    /// for.
+    dialect: Dialect,
+}
+
+impl Migration {
+    pub fn new(table: Table, framework: Framework, dialect: Dialect) -> Self {
+        Self {
+            table,
+            framework,
+            dialect,
+        }
+    }
+
+    fn qualified_name(&self) -> String {
+        self.table.qualified_name().join(".")
+    }
+
+    /// `create_{table}_table`, as the frameworks name their migrations.
+    fn stem(&self) -> String {
+        format!(
+            "create_{}_table",
+            self.table.qualified_name().join("_").to_snake_case()
+        )
+    }
+
+    /// The migration's file name, ordered by `timestamp`, in seconds since
+    /// the Unix epoch.
+    pub fn file_name(&self, timestamp: u64) -> PathBuf {
+        let (year, month, day) = civil_date(timestamp / 86400);
+        let seconds = timestamp % 86400;
+        let (hour, minute, second) = (seconds / 3600, seconds % 3600 / 60, seconds % 60);
+
+        PathBuf::from(match self.framework {
+            Framework::Laravel => format!(
+                "{year:04}_{month:02}_{day:02}_{hour:02}{minute:02}{second:02}_{}.php",
+                self.stem()
+            ),
+            Framework::Phinx => format!(
+                "{year:04}{month:02}{day:02}{hour:02}{minute:02}{second:02}_{}.php",
+                self.stem()
+            ),
+        })
+    }
+
+    /// The single primary key column, if the database increments it.
+    fn increments(&self) -> Option<&Column> {
+        match self.table.primary_key().as_slice() {
+            [key] => self.table.columns.iter().find(|column| {
+                &column.name == key
+                    && column.generated
+                    && matches!(column.sql_type, SqlType::Integer { .. })
+            }),
+            _ => None,
+        }
+    }
+
+    /// Whether the schema builder declares `column`. Generated columns
+    /// other than the incrementing key and columns of types it doesn't know
+    /// are added with SQL once the table exists.
+    fn builds_column(&self, column: &Column) -> bool {
+        let increments = self
+            .increments()
+            .is_some_and(|increments| increments.name == column.name);
+        (increments || !column.generated) && self.column_type(column).is_some()
+    }
+
+    /// Whether every one of `columns` is a column the builder declares.
+    /// Index expressions such as `lower(email)` aren't.
+    fn builds_columns<S: AsRef<str>>(&self, columns: &[S]) -> bool {
+        columns.iter().all(|name| {
+            self.table
+                .columns
+                .iter()
+                .any(|column| column.name == name.as_ref() && self.builds_column(column))
+        })
+    }
+
+    fn builds_constraint(&self, constraint: &Constraint) -> bool {
+        match constraint {
+            Constraint::PrimaryKey { columns, .. } | Constraint::Unique { columns, .. } => {
+                self.builds_columns(columns)
+            }
+            Constraint::ForeignKey(key) => self.builds_foreign_key(key),
+            Constraint::Check { .. } => false,
+        }
+    }
+
+    fn builds_foreign_key(&self, key: &ForeignKey) -> bool {
+        !key.referred_columns.is_empty() && self.builds_columns(&key.columns)
+    }
+
+    fn builds_index(&self, index: &Index) -> bool {
+        self.builds_columns(&index.columns)
+    }
+
+    /// The primary key columns, if the builder declares the key.
+    fn primary_key(&self) -> Vec<&str> {
+        let primary_key = self.table.primary_key();
+        match self.builds_columns(&primary_key) {
+            true => primary_key,
+            false => vec![],
+        }
+    }
+
+    /// What the builder can't express, as SQL run once the table exists:
+    /// columns, then the constraints and indexes it left out.
+    fn leftovers(&self) -> Vec<Change> {
+        let mut changes = vec![];
+        for column in &self.table.columns {
+            if !self.builds_column(column) {
+                changes.extend(migrate::add_column(&self.table, column, self.dialect));
+            }
+        }
+        for constraint in &self.table.constraints {
+            if !self.builds_constraint(constraint) {
+                changes.push(migrate::add_constraint(
+                    &self.table,
+                    constraint,
+                    self.dialect,
+                ));
+            }
+        }
+        for index in &self.table.indexes {
+            if !self.builds_index(index) {
+                changes.push(migrate::create_index(&self.table, index, self.dialect));
+            }
+        }
+
+        changes
+    }
+
+    /// The schema builder's name for `column`'s type, with its arguments.
+    /// Laravel's are `Blueprint` methods, Phinx's are type names and options.
+    fn column_type(&self, column: &Column) -> Option<(&'static str, Vec<String>)> {
+        let laravel = matches!(self.framework, Framework::Laravel);
+        let pick = |laravel_name, phinx_name| match laravel {
+            true => laravel_name,
+            false => phinx_name,
+        };
+        let limit = |length: Option<u64>| match (length, laravel) {
+            (Some(length), true) => vec![length.to_string()],
+            (Some(length), false) => vec![format!("'limit' => {length}")],
+            (None, _) => vec![],
+        };
+
+        Some(match &column.sql_type {
+            SqlType::Integer { bytes, unsigned } => {
+                let name = match (bytes, unsigned, laravel) {
+                    (1, false, _) => pick("tinyInteger", "tinyinteger"),
+                    (1, true, _) => pick("unsignedTinyInteger", "tinyinteger"),
+                    (2, false, _) => pick("smallInteger", "smallinteger"),
+                    (2, true, _) => pick("unsignedSmallInteger", "smallinteger"),
+                    (4, _, true) if matches!(column.data_type, DataType::MediumInt(_)) => {
+                        "mediumInteger"
+                    }
+                    (4, true, true)
+                        if matches!(column.data_type, DataType::MediumIntUnsigned(_)) =>
+                    {
+                        "unsignedMediumInteger"
+                    }
+                    (4, false, _) => pick("integer", "integer"),
+                    (4, true, _) => pick("unsignedInteger", "integer"),
+                    (8, false, _) => pick("bigInteger", "biginteger"),
+                    (8, true, _) => pick("unsignedBigInteger", "biginteger"),
+                    _ => return None,
+                };
+                let options = match (laravel, unsigned) {
+                    (false, true) => vec![String::from("'signed' => false")],
+                    _ => vec![],
+                };
+                (name, options)
+            }
+            SqlType::Float { double } => match double {
+                true => ("double", vec![]),
+                false => ("float", vec![]),
+            },
+            SqlType::Decimal { precision, scale } => {
+                let options = match (precision, laravel) {
+                    (Some(precision), true) => {
+                        vec![precision.to_string(), scale.unwrap_or(0).to_string()]
+                    }
+                    (Some(precision), false) => vec![
+                        format!("'precision' => {precision}"),
+                        format!("'scale' => {}", scale.unwrap_or(0)),
+                    ],
+                    (None, _) => vec![],
+                };
+                ("decimal", options)
+            }
+            SqlType::Boolean => ("boolean", vec![]),
+            SqlType::Char { length } => ("char", limit(length.map(|length| length.value))),
+            SqlType::VarChar { length } => ("string", limit(length.map(|length| length.value))),
+            SqlType::Text => match (&column.data_type, laravel) {
+                (DataType::TinyText, true) => ("tinyText", vec![]),
+                (DataType::MediumText, true) => ("mediumText", vec![]),
+                (DataType::LongText, true) => ("longText", vec![]),
+                _ => ("text", vec![]),
+            },
+            SqlType::Binary { length } => {
+                let fixed = matches!(column.data_type, DataType::Binary(_));
+                match (laravel, length) {
+                    (true, Some(length)) if fixed => {
+                        ("binary", vec![length.to_string(), String::from("true")])
+                    }
+                    (true, length) => ("binary", limit(*length)),
+                    (false, Some(_)) if fixed => ("binary", limit(*length)),
+                    (false, Some(_)) => ("varbinary", limit(*length)),
+                    (false, None) => ("blob", vec![]),
+                }
+            }
+            SqlType::Date => ("date", vec![]),
+            SqlType::Time => ("time", vec![]),
+            SqlType::Timestamp { time_zone: true } => match laravel {
+                true => ("timestampTz", vec![]),
+                false => ("timestamp", vec![String::from("'timezone' => true")]),
+            },
+            SqlType::Timestamp { time_zone: false } => match column.data_type {
+                DataType::Datetime(_) => (pick("dateTime", "datetime"), vec![]),
+                _ => ("timestamp", vec![]),
+            },
+            SqlType::Uuid => ("uuid", vec![]),
+            SqlType::Json => match column.data_type {
+                DataType::JSONB => ("jsonb", vec![]),
+                _ => ("json", vec![]),
+            },
+            SqlType::Enum { values } => {
+                let values: Vec<String> = values.iter().map(|value| php_string(value)).collect();
+                match laravel {
+                    true => ("enum", vec![format!("[{}]", values.join(", "))]),
+                    false => ("enum", vec![format!("'values' => [{}]", values.join(", "))]),
+                }
+            }
+            SqlType::Interval | SqlType::Array { .. } | SqlType::Other { .. } => return None,
+        })
+    }
+
+    fn default(&self, column: &Column) -> Option<ColumnDefault> {
+        column
+            .default
+            .as_ref()
+            .and_then(|expr| ColumnDefault::new(expr, &column.sql_type))
+    }
+
+    /// Whether a declared column's default is an SQL expression the
+    /// migration passes through.
+    fn has_expression_default(&self) -> bool {
+        self.table.columns.iter().any(|column| {
+            self.builds_column(column)
+                && matches!(self.default(column), Some(ColumnDefault::Expression(_)))
+        })
+    }
+
+    /// The `UNIQUE` constraints the builder declares, as their names and
+    /// columns.
+    fn unique_constraints(&self) -> impl Iterator<Item = (&Option<String>, &[String])> {
+        self.table
+            .constraints
+            .iter()
+            .filter(|constraint| self.builds_constraint(constraint))
+            .filter_map(|constraint| match constraint {
+                Constraint::Unique { name, columns } => Some((name, columns.as_slice())),
+                _ => None,
+            })
+    }
+
+    /// The leftovers, run as SQL by the framework after the table is created.
+    fn raw_statements(&self, leftovers: &[Change]) -> String {
+        if leftovers.is_empty() {
+            return String::new();
+        }
+
+        let (call, builder) = match self.framework {
+            Framework::Laravel => ("DB::statement", "schema"),
+            Framework::Phinx => ("$this->execute", "table"),
+        };
+        let mut lines = vec![format!(
+            "\n        // The {builder} builder can't express these, so they're added with SQL.\n"
+        )];
+        for change in leftovers {
+            lines.push(match change {
+                Change::Sql(sql) => format!("        {call}({});\n", php_string(sql)),
+                Change::Note(note) => format!("        // TODO: {note}\n"),
+            });
+        }
+
+        lines.concat()
+    }
+
+    fn laravel(&self) -> String {
+        let leftovers = self.leftovers();
+        let mut imports = vec![
+            "Illuminate\\Database\\Migrations\\Migration",
+            "Illuminate\\Database\\Schema\\Blueprint",
+        ];
+        let raw_sql = leftovers
+            .iter()
+            .any(|change| matches!(change, Change::Sql(_)));
+        if raw_sql || self.has_expression_default() {
+            imports.push("Illuminate\\Support\\Facades\\DB");
+        }
+        imports.push("Illuminate\\Support\\Facades\\Schema");
+
+        let up = format!(
+            "        Schema::create({}, function (Blueprint $table) {{\n{}        }});\n{}",
+            php_string(&self.qualified_name()),
+            self.blueprint(),
+            self.raw_statements(&leftovers)
+        );
+        let imports: String = imports
+            .into_iter()
+            .map(|import| format!("use {import};\n"))
+            .collect();
+
+        format!(
+            "<?php\n\n{imports}\nreturn new class extends Migration {{\n    public function up(): void {{\n{up}    }}\n\n    public function down(): void {{\n        Schema::dropIfExists({});\n    }}\n}};\n",
+            php_string(&self.qualified_name())
+        )
+    }
+
+    /// The `Blueprint` calls defining the table.
+    fn blueprint(&self) -> String {
+        let mut lines = vec![];
+        let primary_key = self.primary_key();
+        let increments = self.increments().map(|column| column.name.as_str());
+
+        for column in &self.table.columns {
+            if !self.builds_column(column) {
+                continue;
+            }
+
+            let mut line = match (increments == Some(column.name.as_str()), &column.sql_type) {
+                (true, SqlType::Integer { bytes: 8, .. }) if column.name == "id" => {
+                    String::from("$table->id()")
+                }
+                (true, SqlType::Integer { bytes, .. }) => {
+                    let method = match bytes {
+                        1 => "tinyIncrements",
+                        2 => "smallIncrements",
+                        4 if matches!(
+                            column.data_type,
+                            DataType::MediumInt(_) | DataType::MediumIntUnsigned(_)
+                        ) =>
+                        {
+                            "mediumIncrements"
+                        }
+                        4 => "increments",
+                        _ => "bigIncrements",
+                    };
+                    format!("$table->{method}({})", php_string(&column.name))
+                }
+                _ => {
+                    let (method, arguments) = self
+                        .column_type(column)
+                        .expect("only declared columns are built");
+                    let mut line = format!(
+                        "$table->{method}({})",
+                        [php_string(&column.name)]
+                            .into_iter()
+                            .chain(arguments)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    );
+                    if column.nullable {
+                        line.push_str("->nullable()");
+                    }
+                    match self.default(column) {
+                        Some(ColumnDefault::Literal(literal)) => {
+                            line.push_str(&format!("->default({literal})"))
+                        }
+                        Some(ColumnDefault::CurrentTimestamp) => line.push_str("->useCurrent()"),
+                        Some(ColumnDefault::Expression(sql)) => {
+                            line.push_str(&format!("->default(DB::raw({}))", php_string(&sql)))
+                        }
+                        None => {}
+                    }
+                    if primary_key == [column.name.as_str()] {
+                        line.push_str("->primary()");
+                    }
+                    line
+                }
+            };
+
+            if self.single_unique(&column.name) {
+                line.push_str("->unique()");
+            }
+            if let Some(comment) = &column.comment {
+                line.push_str(&format!("->comment({})", php_string(comment)));
+            }
+            lines.push(format!("{line};"));
+        }
+
+        if primary_key.len() > 1 {
+            lines.push(format!("$table->primary({});", php_array(&primary_key)));
+        }
+        for (name, columns) in self.unique_constraints() {
+            if name.is_none() && columns.len() == 1 {
+                continue;
+            }
+            lines.push(format!(
+                "$table->unique({}{});",
+                php_array(columns),
+                name_argument(name)
+            ));
+        }
+        for index in self
+            .table
+            .indexes
+            .iter()
+            .filter(|index| self.builds_index(index))
+        {
+            let method = match index.unique {
+                true => "unique",
+                false => "index",
+            };
+            lines.push(format!(
+                "$table->{method}({}{});",
+                php_array(&index.columns),
+                name_argument(&index.name)
+            ));
+        }
+        for key in self
+            .table
+            .foreign_keys()
+            .filter(|key| self.builds_foreign_key(key))
+        {
+            lines.push(laravel_foreign_key(key));
+        }
+        if let Some(comment) = &self.table.comment {
+            lines.push(format!("$table->comment({});", php_string(comment)));
+        }
+
+        lines
+            .iter()
+            .map(|line| format!("            {line}\n"))
+            .collect()
+    }
+
+    /// Whether `column` alone has an unnamed unique constraint, which the
+    /// column declares itself.
+    fn single_unique(&self, column: &str) -> bool {
+        self.unique_constraints()
+            .any(|(name, columns)| name.is_none() && columns == [column])
+    }
+
+    fn phinx(&self) -> String {
+        let class_name = self.stem().to_pascal_case();
+        let mut imports = vec!["Phinx\\Migration\\AbstractMigration"];
+        if self.has_expression_default() {
+            imports.push("Phinx\\Util\\Literal");
+        }
+
+        let table = php_string(&self.qualified_name());
+        let up = format!(
+            "{}{}",
+            self.phinx_table(),
+            self.raw_statements(&self.leftovers())
+        );
+        let imports: String = imports
+            .into_iter()
+            .map(|import| format!("use {import};\n"))
+            .collect();
+
+        format!(
+            "<?php\n\ndeclare(strict_types=1);\n\n{imports}\nfinal class {class_name} extends AbstractMigration {{\n    public function up(): void {{\n{up}    }}\n\n    public function down(): void {{\n        $this->table({table})->drop()->save();\n    }}\n}}\n"
+        )
+    }
+
+    /// The table builder chain creating the table.
+    fn phinx_table(&self) -> String {
+        let primary_key = self.primary_key();
+        let increments = self.increments().map(|column| column.name.as_str());
+
+        // Columns are all declared, so Phinx mustn't add its own `id`.
+        let mut options = vec![String::from("'id' => false")];
+        if !primary_key.is_empty() {
+            options.push(format!("'primary_key' => {}", php_array(&primary_key)));
+        }
+        if let Some(comment) = &self.table.comment {
+            options.push(format!("'comment' => {}", php_string(comment)));
+        }
+
+        let mut calls = vec![];
+        for column in &self.table.columns {
+            if !self.builds_column(column) {
+                continue;
+            }
+
+            let (type_name, mut column_options) = self
+                .column_type(column)
+                .expect("only declared columns are built");
+            if increments == Some(column.name.as_str()) {
+                column_options.insert(0, String::from("'identity' => true"));
+            }
+            if column.nullable {
+                column_options.push(String::from("'null' => true"));
+            }
+            match self.default(column) {
+                Some(ColumnDefault::Literal(literal)) => {
+                    column_options.push(format!("'default' => {literal}"))
+                }
+                Some(ColumnDefault::CurrentTimestamp) => {
+                    column_options.push(String::from("'default' => 'CURRENT_TIMESTAMP'"))
+                }
+                Some(ColumnDefault::Expression(sql)) => {
+                    column_options.push(format!("'default' => Literal::from({})", php_string(&sql)))
+                }
+                None => {}
+            }
+            if let Some(comment) = &column.comment {
+                column_options.push(format!("'comment' => {}", php_string(comment)));
+            }
+
+            let column_options = match column_options.is_empty() {
+                true => String::new(),
+                false => format!(", [{}]", column_options.join(", ")),
+            };
+            calls.push(format!(
+                "addColumn({}, {}{column_options})",
+                php_string(&column.name),
+                php_string(type_name)
+            ));
+        }
+
+        let indexes = self
+            .unique_constraints()
+            .map(|(name, columns)| (name, columns, true))
+            .chain(
+                self.table
+                    .indexes
+                    .iter()
+                    .filter(|index| self.builds_index(index))
+                    .map(|index| (&index.name, index.columns.as_slice(), index.unique)),
+            );
+        for (name, columns, unique) in indexes {
+            let mut index_options = vec![];
+            if unique {
+                index_options.push(String::from("'unique' => true"));
+            }
+            if let Some(name) = name {
+                index_options.push(format!("'name' => {}", php_string(name)));
+            }
+            let index_options = match index_options.is_empty() {
+                true => String::new(),
+                false => format!(", [{}]", index_options.join(", ")),
+            };
+            calls.push(format!("addIndex({}{index_options})", php_array(columns)));
+        }
+
+        for key in self
+            .table
+            .foreign_keys()
+            .filter(|key| self.builds_foreign_key(key))
+        {
+            calls.push(phinx_foreign_key(key));
+        }
+        calls.push(String::from("create()"));
+
+        let calls: String = calls
+            .iter()
+            .map(|call| format!("\n            ->{call}"))
+            .collect();
+        format!(
+            "        $this->table({}, [{}]){calls};\n",
+            php_string(&self.qualified_name()),
+            options.join(", ")
+        )
+    }
+}
+
+impl AsRef<Table> for Migration {
+    fn as_ref(&self) -> &Table {
+        &self.table
+    }
+}
+
+impl Display for Migration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.framework {
+            Framework::Laravel => write!(f, "{}", self.laravel()),
+            Framework::Phinx => write!(f, "{}", self.phinx()),
+        }
+    }
+}
+
+fn laravel_foreign_key(key: &ForeignKey) -> String {
+    let one_or_many = |columns: &[String]| match columns {
+        [column] => php_string(column),
+        columns => php_array(columns),
+    };
+
+    let mut line = format!(
+        "$table->foreign({}{})->references({})->on({})",
+        one_or_many(&key.columns),
+        name_argument(&key.name),
+        one_or_many(&key.referred_columns),
+        php_string(&key.foreign_table.join("."))
+    );
+    if let Some(action) = &key.on_delete {
+        line.push_str(&format!(
+            "->onDelete({})",
+            php_string(&action.to_lowercase())
+        ));
+    }
+    if let Some(action) = &key.on_update {
+        line.push_str(&format!(
+            "->onUpdate({})",
+            php_string(&action.to_lowercase())
+        ));
+    }
+
+    format!("{line};")
+}
+
+fn phinx_foreign_key(key: &ForeignKey) -> String {
+    let one_or_many = |columns: &[String]| match columns {
+        [column] => php_string(column),
+        columns => php_array(columns),
+    };
+
+    // Phinx spells actions with underscores, e.g. `SET_NULL`.
+    let action = |action: &str| php_string(&action.to_uppercase().replace(' ', "_"));
+    let mut options = vec![];
+    if let Some(on_delete) = &key.on_delete {
+        options.push(format!("'delete' => {}", action(on_delete)));
+    }
+    if let Some(on_update) = &key.on_update {
+        options.push(format!("'update' => {}", action(on_update)));
+    }
+    if let Some(name) = &key.name {
+        options.push(format!("'constraint' => {}", php_string(name)));
+    }
+    let options = match options.is_empty() {
+        true => String::new(),
+        false => format!(", [{}]", options.join(", ")),
+    };
+
+    format!(
+        "addForeignKey({}, {}, {}{options})",
+        one_or_many(&key.columns),
+        php_string(&key.foreign_table.join(".")),
+        one_or_many(&key.referred_columns)
+    )
+}
+
+fn php_array<S: AsRef<str>>(items: &[S]) -> String {
+    let items: Vec<String> = items.iter().map(|item| php_string(item.as_ref())).collect();
+    format!("[{}]", items.join(", "))
+}
+
+/// The optional name argument of an index or key method.
+fn name_argument(name: &Option<String>) -> String {
+    match name {
+        Some(name) => format!(", {}", php_string(name)),
+        None => String::new(),
+    }
+}
+
+/// The proleptic Gregorian `(year, month, day)` of `days` since the Unix
+/// epoch.
+fn civil_date(days: u64) -> (u64, u64, u64) {
+    // Howard Hinnant's `civil_from_days`, with eras starting on March 1st.
+    let z = days + 719468;
+    let era = z / 146097;
+    let day_of_era = z - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lang::testing::{assert_golden, table};
+
+    fn migrations(framework: Framework) -> Vec<Migration> {
+        [
+            "CREATE TABLE posts (
+                id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+                user_id INT NOT NULL,
+                title VARCHAR(200) NOT NULL DEFAULT '' COMMENT 'Headline',
+                body MEDIUMTEXT,
+                price DECIMAL(8, 2) DEFAULT -1.5,
+                published BOOLEAN NOT NULL DEFAULT 0,
+                status ENUM('draft', 'live') NOT NULL,
+                created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                CONSTRAINT posts_user FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+                CONSTRAINT posts_title UNIQUE (user_id, title)
+            ) COMMENT = 'Blog posts';
+            CREATE INDEX posts_created_at ON posts (created_at);",
+            "CREATE TABLE users (
+                id INT PRIMARY KEY,
+                email VARCHAR(100) NOT NULL UNIQUE,
+                balance INT NOT NULL CHECK (balance >= 0),
+                tags TEXT[],
+                email_domain TEXT GENERATED ALWAYS AS (split_part(email, '@', 2)) STORED,
+                token UUID DEFAULT gen_random_uuid()
+            );
+            CREATE INDEX users_lower_email ON users (lower(email));",
+        ]
+        .into_iter()
+        .map(|sql| Migration::new(table(sql), framework, Dialect::Postgres))
+        .collect()
+    }
+
+    #[test]
+    fn laravel() {
+        let migrations = migrations(Framework::Laravel);
+        assert_golden("migration/laravel_posts.php", &migrations[0].to_string());
+        assert_golden("migration/laravel_users.php", &migrations[1].to_string());
+    }
+
+    #[test]
+    fn phinx() {
+        let migrations = migrations(Framework::Phinx);
+        assert_golden("migration/phinx_posts.php", &migrations[0].to_string());
+        assert_golden("migration/phinx_users.php", &migrations[1].to_string());
+    }
+
+    #[test]
+    fn file_names() {
+        // 2024-02-29 13:45:30 UTC.
+        let timestamp = 1709214330;
+        assert_eq!(
+            migrations(Framework::Laravel)[0].file_name(timestamp),
+            PathBuf::from("2024_02_29_134530_create_posts_table.php")
+        );
+        assert_eq!(
+            migrations(Framework::Phinx)[0].file_name(timestamp),
+            PathBuf::from("20240229134530_create_posts_table.php")
+        );
+    }
+
+    #[test]
+    fn dependency_order() {
+        let names: Vec<String> = crate::sql::ir::dependency_order(migrations(Framework::Laravel))
+            .iter()
+            .map(|migration| migration.qualified_name())
+            .collect();
+        assert_eq!(names, ["users", "posts"]);
+    }
+
+    #[test]
+    fn civil_dates() {
+        assert_eq!(civil_date(0), (1970, 1, 1));
+        // Leap days, in a year divisible by 4 and in one divisible by 400.
+        assert_eq!(civil_date(19782), (2024, 2, 29));
+        assert_eq!(civil_date(11016), (2000, 2, 29));
+        assert_eq!(civil_date(11017), (2000, 3, 1));
+        // Year boundaries.
+        assert_eq!(civil_date(10956), (1999, 12, 31));
+        assert_eq!(civil_date(10957), (2000, 1, 1));
+        assert_eq!(civil_date(20088), (2024, 12, 31));
+        assert_eq!(civil_date(20089), (2025, 1, 1));
+    }
+}
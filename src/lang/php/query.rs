@@ -0,0 +1,1028 @@
+use anyhow::{Context, anyhow};
+use sqlparser::ast::{
+    AssignmentTarget, Expr, FromTable, FunctionArg, FunctionArgExpr, FunctionArguments,
+    GroupByExpr, Ident, JoinConstraint, JoinOperator, LimitClause, ObjectName, OnConflict,
+    OnConflictAction, OnInsert, OrderByKind, Query, SelectItem, SelectItemQualifiedWildcardKind,
+    SetExpr, Statement, TableFactor, TableObject, TableWithJoins, Value,
+};
+use sqlparser::dialect::GenericDialect;
+use sqlparser::tokenizer::{Token, Tokenizer};
+use std::fmt::Display;
+
+use super::{Class, Field, Kind, php_string, to_sql_value};
+use crate::lang::PascalCase;
+use crate::sql::ir::SqlType;
+use crate::sql::query::{NamedQuery, QueryCommand};
+use row::{Row, Scope};
+
+mod row;
+
+/// The tables known from the schema files, used to resolve the columns a
+/// query refers to.
+pub struct Catalog<'a> {
+    classes: &'a [Class],
+    /// The namespace the query classes are generated in.
+    namespace: &'a str,
+    /// Whether rows holding a whole table are hydrated into its model with
+    /// `fromRow()`, rather than a row class of their own.
+    models: bool,
+}
+
+impl<'a> Catalog<'a> {
+    pub fn new(classes: &'a [Class], namespace: &'a str, models: bool) -> Self {
+        Self {
+            classes,
+            namespace,
+            models,
+        }
+    }
+
+    /// Finds the table `name` refers to. An unqualified name matches a table
+    /// in any schema.
+    fn table(&self, name: &ObjectName) -> Option<&'a Class> {
+        let parts: Vec<&str> = name
+            .0
+            .iter()
+            .filter_map(|part| part.as_ident())
+            .map(|ident| ident.value.as_str())
+            .collect();
+        let (table, schema) = parts.split_last()?;
+
+        self.classes.iter().find(|class| {
+            class.name == *table
+                && (schema.is_empty()
+                    || class
+                        .schema
+                        .iter()
+                        .map(String::as_str)
+                        .eq(schema.iter().copied()))
+        })
+    }
+
+    /// The tables a `FROM` clause brings into scope. Tables the schema
+    /// doesn't describe are left out.
+    fn scope(&self, from: &[TableWithJoins]) -> Vec<ScopeTable<'a>> {
+        relations(from)
+            .into_iter()
+            .filter_map(|(relation, nullable)| match relation {
+                TableFactor::Table { name, alias, .. } => {
+                    let class = self.table(name)?;
+                    Some(ScopeTable {
+                        name: alias
+                            .as_ref()
+                            .map(|alias| alias.name.value.clone())
+                            .unwrap_or_else(|| class.name.clone()),
+                        class,
+                        nullable,
+                    })
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Every relation in a `FROM` clause, paired with whether an outer join can
+/// leave its columns NULL.
+fn relations(from: &[TableWithJoins]) -> Vec<(&TableFactor, bool)> {
+    let mut relations = vec![];
+    for table in from {
+        let start = relations.len();
+        relations.push((&table.relation, false));
+
+        for join in &table.joins {
+            let (left, right) = match join.join_operator {
+                JoinOperator::Left(_) | JoinOperator::LeftOuter(_) => (false, true),
+                JoinOperator::Right(_) | JoinOperator::RightOuter(_) => (true, false),
+                JoinOperator::FullOuter(_) => (true, true),
+                _ => (false, false),
+            };
+            if left {
+                for relation in &mut relations[start..] {
+                    relation.1 = true;
+                }
+            }
+            relations.push((&join.relation, right));
+        }
+    }
+
+    relations
+}
+
+/// A table visible to a query, under its alias if it has one.
+#[derive(Clone)]
+struct ScopeTable<'a> {
+    name: String,
+    class: &'a Class,
+    /// Whether an outer join can leave this table's columns NULL.
+    nullable: bool,
+}
+
+/// A value the caller passes for a placeholder.
+#[derive(Debug)]
+struct Param {
+    name: String,
+    kind: Kind,
+    json: bool,
+    nullable: bool,
+}
+
+/// The column a placeholder is compared with or assigned to, which decides
+/// the parameter's name and type.
+#[derive(Debug, Clone)]
+struct Hint {
+    name: String,
+    kind: Kind,
+    json: bool,
+    nullable: bool,
+}
+
+impl Hint {
+    fn from_field(field: &Field) -> Self {
+        Self {
+            name: field.name.clone(),
+            kind: field.type_annotation,
+            json: field.is_json(),
+            nullable: field.nullable,
+        }
+    }
+
+    fn int(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            kind: Kind::Int,
+            json: false,
+            nullable: false,
+        }
+    }
+
+    /// Comparisons never match NULL, so the value compared with a nullable
+    /// column is still required.
+    fn required(mut self) -> Self {
+        self.nullable = false;
+        self
+    }
+}
+
+/// Walks a statement in source order, collecting a parameter for every
+/// placeholder.
+struct Params<'c, 'a> {
+    catalog: &'c Catalog<'a>,
+    scopes: Vec<Vec<ScopeTable<'a>>>,
+    params: Vec<Param>,
+    /// Whether the statement uses `:name` placeholders rather than `?`.
+    named: Option<bool>,
+    error: Option<anyhow::Error>,
+}
+
+impl<'c, 'a> Params<'c, 'a> {
+    fn new(catalog: &'c Catalog<'a>) -> Self {
+        Self {
+            catalog,
+            scopes: vec![],
+            params: vec![],
+            named: None,
+            error: None,
+        }
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Query(query) => self.query(query),
+            Statement::Insert(insert) => {
+                let table = match &insert.table {
+                    TableObject::TableName(name) => self.catalog.table(name),
+                    TableObject::TableFunction(_) => None,
+                };
+                self.push_scope(table.map(|class| ScopeTable {
+                    name: class.name.clone(),
+                    class,
+                    nullable: false,
+                }));
+
+                let hints: Vec<Option<Hint>> = insert
+                    .columns
+                    .iter()
+                    .map(|column| self.resolve(std::slice::from_ref(column)))
+                    .collect();
+
+                if let Some(source) = &insert.source {
+                    match source.body.as_ref() {
+                        SetExpr::Values(values) => {
+                            for row in &values.rows {
+                                for (i, expr) in row.iter().enumerate() {
+                                    self.expr(expr, hints.get(i).cloned().flatten());
+                                }
+                            }
+                        }
+                        _ => self.query(source),
+                    }
+                }
+
+                for assignment in &insert.assignments {
+                    self.assignment(&assignment.target, &assignment.value);
+                }
+                match &insert.on {
+                    Some(OnInsert::DuplicateKeyUpdate(assignments)) => {
+                        for assignment in assignments {
+                            self.assignment(&assignment.target, &assignment.value);
+                        }
+                    }
+                    Some(OnInsert::OnConflict(OnConflict {
+                        action: OnConflictAction::DoUpdate(update),
+                        ..
+                    })) => {
+                        for assignment in &update.assignments {
+                            self.assignment(&assignment.target, &assignment.value);
+                        }
+                        if let Some(selection) = &update.selection {
+                            self.expr(selection, None);
+                        }
+                    }
+                    _ => {}
+                }
+                self.scopes.pop();
+            }
+            Statement::Update {
+                table,
+                assignments,
+                selection,
+                ..
+            } => {
+                let tables = self.catalog.scope(std::slice::from_ref(table));
+                self.scopes.push(tables);
+                for assignment in assignments {
+                    self.assignment(&assignment.target, &assignment.value);
+                }
+                if let Some(selection) = selection {
+                    self.expr(selection, None);
+                }
+                self.scopes.pop();
+            }
+            Statement::Delete(delete) => {
+                let (FromTable::WithFromKeyword(from) | FromTable::WithoutKeyword(from)) =
+                    &delete.from;
+                let tables = self.catalog.scope(from);
+                self.scopes.push(tables);
+                if let Some(selection) = &delete.selection {
+                    self.expr(selection, None);
+                }
+                if let Some(limit) = &delete.limit {
+                    self.expr(limit, Some(Hint::int("limit")));
+                }
+                self.scopes.pop();
+            }
+            _ => {}
+        }
+    }
+
+    fn push_scope(&mut self, table: Option<ScopeTable<'a>>) {
+        self.scopes.push(table.into_iter().collect());
+    }
+
+    fn assignment(&mut self, target: &AssignmentTarget, value: &Expr) {
+        let hint = match target {
+            AssignmentTarget::ColumnName(name) => {
+                let idents: Vec<Ident> = name
+                    .0
+                    .iter()
+                    .filter_map(|part| part.as_ident().cloned())
+                    .collect();
+                self.resolve(&idents)
+            }
+            AssignmentTarget::Tuple(_) => None,
+        };
+        self.expr(value, hint);
+    }
+
+    fn query(&mut self, query: &Query) {
+        if let Some(with) = &query.with {
+            for cte in &with.cte_tables {
+                self.query(&cte.query);
+            }
+        }
+        self.set_expr(&query.body);
+
+        if let Some(order_by) = &query.order_by
+            && let OrderByKind::Expressions(exprs) = &order_by.kind
+        {
+            for expr in exprs {
+                self.expr(&expr.expr, None);
+            }
+        }
+
+        match &query.limit_clause {
+            Some(LimitClause::LimitOffset { limit, offset, .. }) => {
+                if let Some(limit) = limit {
+                    self.expr(limit, Some(Hint::int("limit")));
+                }
+                if let Some(offset) = offset {
+                    self.expr(&offset.value, Some(Hint::int("offset")));
+                }
+            }
+            Some(LimitClause::OffsetCommaLimit { offset, limit }) => {
+                self.expr(offset, Some(Hint::int("offset")));
+                self.expr(limit, Some(Hint::int("limit")));
+            }
+            None => {}
+        }
+    }
+
+    fn set_expr(&mut self, body: &SetExpr) {
+        match body {
+            SetExpr::Select(select) => {
+                let tables = self.catalog.scope(&select.from);
+                self.scopes.push(tables);
+
+                for item in &select.projection {
+                    if let SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. } =
+                        item
+                    {
+                        self.expr(expr, None);
+                    }
+                }
+                for table in &select.from {
+                    self.relation(&table.relation);
+                    for join in &table.joins {
+                        self.relation(&join.relation);
+                        if let Some(JoinConstraint::On(expr)) = join_constraint(&join.join_operator)
+                        {
+                            self.expr(expr, None);
+                        }
+                    }
+                }
+                if let Some(selection) = &select.selection {
+                    self.expr(selection, None);
+                }
+                if let GroupByExpr::Expressions(exprs, _) = &select.group_by {
+                    for expr in exprs {
+                        self.expr(expr, None);
+                    }
+                }
+                if let Some(having) = &select.having {
+                    self.expr(having, None);
+                }
+
+                self.scopes.pop();
+            }
+            SetExpr::Query(query) => self.query(query),
+            SetExpr::SetOperation { left, right, .. } => {
+                self.set_expr(left);
+                self.set_expr(right);
+            }
+            _ => {}
+        }
+    }
+
+    /// Walks a subquery in `FROM`.
+    fn relation(&mut self, relation: &TableFactor) {
+        if let TableFactor::Derived { subquery, .. } = relation {
+            self.query(subquery);
+        }
+    }
+
+    /// Resolves a possibly qualified column reference against the innermost
+    /// scope that knows it.
+    fn resolve(&self, idents: &[Ident]) -> Option<Hint> {
+        let (column, qualifier) = idents.split_last()?;
+        let qualifier = qualifier.last().map(|ident| ident.value.as_str());
+
+        self.scopes.iter().rev().find_map(|scope| {
+            scope
+                .iter()
+                .filter(|table| qualifier.is_none_or(|q| q == table.name))
+                .find_map(|table| {
+                    table
+                        .class
+                        .fields
+                        .iter()
+                        .find(|field| field.name == column.value)
+                })
+                .map(Hint::from_field)
+        })
+    }
+
+    /// The column an expression refers to, if it is a plain column reference.
+    fn hint_of(&self, expr: &Expr) -> Option<Hint> {
+        match expr {
+            Expr::Identifier(ident) => self.resolve(std::slice::from_ref(ident)),
+            Expr::CompoundIdentifier(idents) => self.resolve(idents),
+            Expr::Nested(expr) => self.hint_of(expr),
+            _ => None,
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr, hint: Option<Hint>) {
+        match expr {
+            Expr::Value(v) => {
+                if let Value::Placeholder(placeholder) = &v.value {
+                    self.placeholder(placeholder, hint);
+                }
+            }
+            Expr::BinaryOp { left, right, .. } => {
+                let left_hint = self.hint_of(right).map(Hint::required);
+                let right_hint = self.hint_of(left).map(Hint::required);
+                self.expr(left, left_hint);
+                self.expr(right, right_hint);
+            }
+            Expr::Between {
+                expr, low, high, ..
+            } => {
+                let hint = self.hint_of(expr).map(Hint::required);
+                self.expr(expr, None);
+                self.expr(low, hint.clone());
+                self.expr(high, hint);
+            }
+            Expr::InList { expr, list, .. } => {
+                let hint = self.hint_of(expr).map(Hint::required);
+                self.expr(expr, None);
+                for item in list {
+                    self.expr(item, hint.clone());
+                }
+            }
+            Expr::Like { expr, pattern, .. } | Expr::ILike { expr, pattern, .. } => {
+                let hint = self.hint_of(expr).map(|hint| Hint {
+                    kind: Kind::String,
+                    ..hint.required()
+                });
+                self.expr(expr, None);
+                self.expr(pattern, hint);
+            }
+            Expr::Nested(expr) | Expr::UnaryOp { expr, .. } => self.expr(expr, hint),
+            Expr::IsNull(expr)
+            | Expr::IsNotNull(expr)
+            | Expr::IsTrue(expr)
+            | Expr::IsFalse(expr)
+            | Expr::IsNotTrue(expr)
+            | Expr::IsNotFalse(expr) => self.expr(expr, None),
+            Expr::Cast {
+                expr, data_type, ..
+            } => {
+                let hint = hint.map(|hint| Hint {
+                    kind: Kind::from(&SqlType::from(data_type)),
+                    ..hint
+                });
+                self.expr(expr, hint);
+            }
+            Expr::Function(function) => {
+                if let FunctionArguments::List(list) = &function.args {
+                    for arg in &list.args {
+                        if let FunctionArg::Unnamed(FunctionArgExpr::Expr(expr))
+                        | FunctionArg::Named {
+                            arg: FunctionArgExpr::Expr(expr),
+                            ..
+                        } = arg
+                        {
+                            self.expr(expr, None);
+                        }
+                    }
+                }
+            }
+            Expr::Case {
+                operand,
+                conditions,
+                else_result,
+            } => {
+                if let Some(operand) = operand {
+                    self.expr(operand, None);
+                }
+                for when in conditions {
+                    self.expr(&when.condition, None);
+                    self.expr(&when.result, None);
+                }
+                if let Some(else_result) = else_result {
+                    self.expr(else_result, None);
+                }
+            }
+            Expr::InSubquery { expr, subquery, .. } => {
+                self.expr(expr, None);
+                self.set_expr(subquery);
+            }
+            Expr::Exists { subquery, .. } | Expr::Subquery(subquery) => self.query(subquery),
+            _ => {}
+        }
+    }
+
+    fn placeholder(&mut self, placeholder: &str, hint: Option<Hint>) {
+        let named = match placeholder.strip_prefix(':') {
+            Some(_) => true,
+            None if placeholder == "?" => false,
+            None => {
+                self.error.get_or_insert(anyhow!(
+                    "unsupported placeholder `{placeholder}`: use `?` or `:name`"
+                ));
+                return;
+            }
+        };
+
+        if *self.named.get_or_insert(named) != named {
+            self.error
+                .get_or_insert(anyhow!("a query can't mix `?` and `:name` placeholders"));
+            return;
+        }
+
+        let hint = hint.unwrap_or(Hint {
+            name: String::from("param"),
+            kind: Kind::Mixed,
+            json: false,
+            nullable: true,
+        });
+
+        if let Some(name) = placeholder.strip_prefix(':') {
+            // A named placeholder used twice is still a single argument.
+            if self.params.iter().any(|param| param.name == name) {
+                return;
+            }
+            self.params.push(Param {
+                name: name.to_string(),
+                kind: hint.kind,
+                json: hint.json,
+                nullable: hint.nullable,
+            });
+            return;
+        }
+
+        let mut name = hint.name.clone();
+        let mut suffix = 1;
+        while self.params.iter().any(|param| param.name == name) {
+            suffix += 1;
+            name = format!("{}{suffix}", hint.name);
+        }
+        self.params.push(Param {
+            name,
+            kind: hint.kind,
+            json: hint.json,
+            nullable: hint.nullable,
+        });
+    }
+}
+
+/// The number of arguments `statement` takes: one per `?`, or one per
+/// distinct `:name`.
+fn placeholders(statement: &Statement) -> anyhow::Result<usize> {
+    let sql = statement.to_string();
+    let tokens = Tokenizer::new(&GenericDialect {}, &sql).tokenize()?;
+    // The tokenizer splits `:name` into a colon and a word.
+    let mut placeholders: Vec<String> = tokens
+        .iter()
+        .zip(tokens.iter().skip(1).map(Some).chain([None]))
+        .filter_map(|pair| match pair {
+            (Token::Placeholder(placeholder), _) => Some(placeholder.clone()),
+            (Token::Colon, Some(Token::Word(word))) => Some(format!(":{}", word.value)),
+            _ => None,
+        })
+        .collect();
+    if placeholders.iter().all(|placeholder| placeholder != "?") {
+        placeholders.sort();
+        placeholders.dedup();
+    }
+
+    Ok(placeholders.len())
+}
+
+fn join_constraint(operator: &JoinOperator) -> Option<&JoinConstraint> {
+    match operator {
+        JoinOperator::Join(constraint)
+        | JoinOperator::Inner(constraint)
+        | JoinOperator::Left(constraint)
+        | JoinOperator::LeftOuter(constraint)
+        | JoinOperator::Right(constraint)
+        | JoinOperator::RightOuter(constraint)
+        | JoinOperator::FullOuter(constraint)
+        | JoinOperator::Semi(constraint)
+        | JoinOperator::LeftSemi(constraint)
+        | JoinOperator::RightSemi(constraint)
+        | JoinOperator::Anti(constraint)
+        | JoinOperator::LeftAnti(constraint)
+        | JoinOperator::RightAnti(constraint) => Some(constraint),
+        _ => None,
+    }
+}
+
+/// What a query method hands back for each row.
+enum Output<'a> {
+    /// The query doesn't return rows.
+    None,
+    /// Every column of a single table, hydrated into its model.
+    Model(&'a Class),
+    /// Any other columns, hydrated into a row class generated for the query,
+    /// or returned as an associative array when they can't be inferred.
+    Row(Option<Row>),
+}
+
+impl<'a> Output<'a> {
+    /// `row_class` names the row class generated if no model fits.
+    fn new(
+        row_class: String,
+        statement: &Statement,
+        catalog: &Catalog<'a>,
+    ) -> anyhow::Result<Self> {
+        let (projection, tables, complete) = match statement {
+            Statement::Query(query) => match first_select(&query.body) {
+                Some(select) => {
+                    let tables = catalog.scope(&select.from);
+                    let complete = tables.len() == relations(&select.from).len();
+                    (&select.projection, tables, complete)
+                }
+                None => return Ok(Self::Row(None)),
+            },
+            Statement::Insert(insert) => match (&insert.returning, &insert.table) {
+                (Some(returning), TableObject::TableName(name)) => {
+                    let tables: Vec<ScopeTable> = catalog
+                        .table(name)
+                        .map(|class| ScopeTable {
+                            name: class.name.clone(),
+                            class,
+                            nullable: false,
+                        })
+                        .into_iter()
+                        .collect();
+                    let complete = !tables.is_empty();
+                    (returning, tables, complete)
+                }
+                (Some(_), _) => return Ok(Self::Row(None)),
+                (None, _) => return Ok(Self::None),
+            },
+            Statement::Update {
+                table,
+                returning: Some(returning),
+                ..
+            } => {
+                let from = std::slice::from_ref(table);
+                let tables = catalog.scope(from);
+                let complete = tables.len() == relations(from).len();
+                (returning, tables, complete)
+            }
+            Statement::Delete(delete) => match (&delete.returning, &delete.from) {
+                (
+                    Some(returning),
+                    FromTable::WithFromKeyword(from) | FromTable::WithoutKeyword(from),
+                ) => {
+                    let tables = catalog.scope(from);
+                    let complete = tables.len() == relations(from).len();
+                    (returning, tables, complete)
+                }
+                (None, _) => return Ok(Self::None),
+            },
+            _ => return Ok(Self::None),
+        };
+
+        // A table on the optional side of an outer join can't fill a model,
+        // whose columns may be required.
+        let model = match (projection.as_slice(), tables.as_slice()) {
+            ([SelectItem::Wildcard(_)], [table]) if complete => Some(table),
+            (
+                [
+                    SelectItem::QualifiedWildcard(
+                        SelectItemQualifiedWildcardKind::ObjectName(name),
+                        _,
+                    ),
+                ],
+                _,
+            ) => {
+                let alias = name.0.last().and_then(|part| part.as_ident());
+                tables
+                    .iter()
+                    .find(|table| alias.is_some_and(|alias| alias.value == table.name))
+            }
+            _ => None,
+        };
+        if let Some(table) = model.filter(|table| !table.nullable && catalog.models) {
+            return Ok(Self::Model(table.class));
+        }
+
+        let scope = Scope { catalog, tables };
+        Ok(Self::Row(Row::new(
+            row_class, projection, &scope, complete,
+        )?))
+    }
+
+    /// The class each row is hydrated into, as referenced from a query class
+    /// in `namespace`.
+    fn class_ref(&self, namespace: &str) -> Option<String> {
+        match self {
+            Output::Model(class) if class.namespace == namespace => Some(class.class_name.clone()),
+            Output::Model(class) => Some(format!("\\{}\\{}", class.namespace, class.class_name)),
+            Output::Row(Some(row)) => Some(row.class_name.clone()),
+            Output::Row(None) | Output::None => None,
+        }
+    }
+}
+
+fn first_select(body: &SetExpr) -> Option<&sqlparser::ast::Select> {
+    match body {
+        SetExpr::Select(select) => Some(select),
+        SetExpr::Query(query) => first_select(&query.body),
+        SetExpr::SetOperation { left, .. } => first_select(left),
+        _ => None,
+    }
+}
+
+struct Method<'a> {
+    name: String,
+    command: QueryCommand,
+    sql: String,
+    params: Vec<Param>,
+    named: bool,
+    output: Output<'a>,
+    namespace: &'a str,
+}
+
+impl Method<'_> {
+    fn signature(&self) -> String {
+        self.params
+            .iter()
+            .map(|param| {
+                // `mixed` already includes null and can't be made nullable.
+                let null = match param.kind {
+                    Kind::Mixed => "",
+                    _ if param.nullable => "?",
+                    _ => "",
+                };
+                format!("{null}{} ${}", param.kind, param.name)
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Binds each parameter with its PDO type, so integers stay integers
+    /// where MySQL requires them, such as in `LIMIT`.
+    fn bindings(&self) -> String {
+        self.params
+            .iter()
+            .enumerate()
+            .map(|(i, param)| {
+                let name = format!("${}", param.name);
+                let (value, pdo_type) = match param.kind {
+                    Kind::Int => (name, "PARAM_INT"),
+                    Kind::Bool => (name, "PARAM_BOOL"),
+                    _ => (
+                        to_sql_value(&param.kind, param.json, param.nullable, &name),
+                        "PARAM_STR",
+                    ),
+                };
+                let key = match self.named {
+                    true => php_string(&format!(":{}", param.name)),
+                    false => (i + 1).to_string(),
+                };
+                format!("        $stmt->bindValue({key}, {value}, \\PDO::{pdo_type});\n")
+            })
+            .collect()
+    }
+}
+
+impl Display for Method<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (doc, return_type, result) = match (
+            &self.command,
+            self.output.class_ref(self.namespace),
+        ) {
+            (QueryCommand::One, Some(model)) => (
+                None,
+                format!("?{model}"),
+                format!(
+                    "$row = $stmt->fetch(\\PDO::FETCH_ASSOC);\n\n        return $row === false ? null : {model}::fromRow($row);"
+                ),
+            ),
+            (QueryCommand::Many, Some(model)) => (
+                Some(format!("@return {model}[]")),
+                String::from("array"),
+                format!(
+                    "return array_map(\n            fn (array $row): {model} => {model}::fromRow($row),\n            $stmt->fetchAll(\\PDO::FETCH_ASSOC),\n        );"
+                ),
+            ),
+            (QueryCommand::One, _) => (
+                Some(String::from("@return array<string, mixed>|null")),
+                String::from("?array"),
+                String::from(
+                    "$row = $stmt->fetch(\\PDO::FETCH_ASSOC);\n\n        return $row === false ? null : $row;",
+                ),
+            ),
+            (QueryCommand::Many, _) => (
+                Some(String::from("@return array<string, mixed>[]")),
+                String::from("array"),
+                String::from("return $stmt->fetchAll(\\PDO::FETCH_ASSOC);"),
+            ),
+            (QueryCommand::Exec, _) => (None, String::from("void"), String::new()),
+            (QueryCommand::ExecRows, _) => (
+                None,
+                String::from("int"),
+                String::from("return $stmt->rowCount();"),
+            ),
+            (QueryCommand::ExecLastId, _) => (
+                None,
+                String::from("string|false"),
+                String::from("return $this->pdo->lastInsertId();"),
+            ),
+        };
+
+        if let Some(doc) = doc {
+            writeln!(f, "    /** {doc} */")?;
+        }
+        write!(
+            f,
+            "    public function {}({}): {return_type} {{\n        $stmt = $this->pdo->prepare({});\n{}        $stmt->execute();\n",
+            self.name,
+            self.signature(),
+            php_string(&self.sql),
+            self.bindings()
+        )?;
+        if !result.is_empty() {
+            write!(f, "\n        {result}\n")?;
+        }
+        writeln!(f, "    }}")
+    }
+}
+
+/// A class with one typed method per query in a query file.
+pub struct Queries<'a> {
+    pub class_name: String,
+    methods: Vec<Method<'a>>,
+}
+
+impl<'a> Queries<'a> {
+    pub fn new(name: &str, queries: &[NamedQuery], catalog: &Catalog<'a>) -> anyhow::Result<Self> {
+        let mut methods = vec![];
+
+        for query in queries {
+            let mut params = Params::new(catalog);
+            params.statement(&query.statement);
+            if let Some(error) = params.error {
+                return Err(error.context(format!("in query {}", query.name)));
+            }
+            let placeholders = placeholders(&query.statement)?;
+            if placeholders != params.params.len() {
+                return Err(anyhow!(
+                    "query {} has {placeholders} placeholders but only {} are where quaff looks \
+                     for parameters",
+                    query.name,
+                    params.params.len()
+                ));
+            }
+
+            let mut chars = query.name.chars();
+            let (method_name, row_class) = match chars.next() {
+                Some(first) => (
+                    format!("{}{}", first.to_ascii_lowercase(), chars.as_str()),
+                    format!("{}{}Row", first.to_ascii_uppercase(), chars.as_str()),
+                ),
+                None => (String::new(), String::new()),
+            };
+
+            let output = match query.command {
+                QueryCommand::One | QueryCommand::Many => {
+                    Output::new(row_class, &query.statement, catalog)
+                        .with_context(|| format!("in query {}", query.name))?
+                }
+                _ => Output::None,
+            };
+            if matches!(query.command, QueryCommand::One | QueryCommand::Many)
+                && matches!(output, Output::None)
+            {
+                return Err(anyhow!(
+                    "query {} is annotated as returning rows but has no result columns",
+                    query.name
+                ));
+            }
+
+            methods.push(Method {
+                name: method_name,
+                command: query.command,
+                sql: query.statement.to_string(),
+                params: params.params,
+                named: params.named.unwrap_or(false),
+                output,
+                namespace: catalog.namespace,
+            });
+        }
+
+        Ok(Self {
+            class_name: format!("{}Queries", name.to_pascal_case()),
+            methods,
+        })
+    }
+
+    /// The classes of the rows the queries return, as `(class name, class)`
+    /// pairs, since each goes in a file of its own.
+    pub fn rows(&self) -> Vec<(&str, String)> {
+        self.methods
+            .iter()
+            .filter_map(|method| match &method.output {
+                Output::Row(Some(row)) => Some((row.class_name.as_str(), row.to_string())),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl Display for Queries<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let methods = self
+            .methods
+            .iter()
+            .map(|method| method.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        write!(
+            f,
+            "class {} {{\n    public function __construct(private \\PDO $pdo) {{ }}\n\n{methods}}}",
+            self.class_name
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lang::testing::table;
+
+    fn signatures(queries: &[&str]) -> anyhow::Result<Vec<String>> {
+        let classes = [
+            Class::new(&table(
+                "CREATE TABLE users (id INT PRIMARY KEY, email VARCHAR(100) NOT NULL, bio TEXT);",
+            )),
+            Class::new(&table(
+                "CREATE TABLE posts (id INT PRIMARY KEY, user_id INT NOT NULL, meta JSON);",
+            )),
+        ];
+        let catalog = Catalog::new(&classes, "App\\Models", true);
+        let queries: Vec<NamedQuery> = queries
+            .iter()
+            .enumerate()
+            .map(|(i, sql)| NamedQuery {
+                name: format!("Query{i}"),
+                command: QueryCommand::Exec,
+                statement: crate::sql::parse_sql(sql).unwrap().remove(0),
+            })
+            .collect();
+
+        let queries = Queries::new("users", &queries, &catalog)?;
+        Ok(queries.methods.iter().map(Method::signature).collect())
+    }
+
+    #[test]
+    fn params_take_their_columns_types() {
+        let signatures = signatures(&[
+            "SELECT * FROM users WHERE email = ? AND bio LIKE ?",
+            "INSERT INTO users (email, bio) VALUES (?, ?)",
+            "UPDATE users SET bio = :bio WHERE id = :id OR id = :id",
+            "SELECT * FROM users u JOIN posts p ON p.user_id = u.id WHERE p.id IN (?, ?) LIMIT ?",
+            "DELETE FROM posts WHERE meta = ? OR 1 = ?",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            signatures,
+            [
+                "string $email, string $bio",
+                "string $email, ?string $bio",
+                "?string $bio, int $id",
+                "int $id, int $id2, int $limit",
+                "array $meta, mixed $param",
+            ]
+        );
+    }
+
+    #[test]
+    fn params_anywhere_in_the_statement() {
+        let signatures = signatures(&[
+            "WITH recent AS (SELECT * FROM posts WHERE id > ?) SELECT * FROM recent LIMIT ?",
+            "SELECT u.id FROM users u JOIN (SELECT * FROM posts WHERE user_id = ?) p ON p.id = u.id",
+            "SELECT email, COUNT(*) FROM users GROUP BY email, id > ? HAVING COUNT(*) > ?",
+            "INSERT INTO users (id, email) VALUES (?, ?) ON DUPLICATE KEY UPDATE email = ?",
+            "INSERT INTO users (id, email) VALUES (:id, :email)
+             ON CONFLICT (id) DO UPDATE SET bio = :bio WHERE users.email <> :email",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            signatures,
+            [
+                "int $id, int $limit",
+                "int $user_id",
+                "int $id, mixed $param",
+                "int $id, string $email, string $email2",
+                "int $id, string $email, ?string $bio",
+            ]
+        );
+    }
+
+    #[test]
+    fn unread_placeholders_are_an_error() {
+        let err = signatures(&["SELECT ROW_NUMBER() OVER (ORDER BY ?) FROM users"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "query Query0 has 1 placeholders but only 0 are where quaff looks for parameters"
+        );
+    }
+
+    #[test]
+    fn placeholders_cant_be_mixed() {
+        let err = signatures(&["SELECT * FROM users WHERE id = ? AND email = :email"]).unwrap_err();
+        assert_eq!(
+            format!("{err:#}"),
+            "in query Query0: a query can't mix `?` and `:name` placeholders"
+        );
+    }
+}
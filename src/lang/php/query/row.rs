@@ -0,0 +1,444 @@
+use anyhow::anyhow;
+use sqlparser::ast::{
+    BinaryOperator, CastKind, Expr, Function, FunctionArg, FunctionArgExpr, FunctionArguments,
+    Ident, SelectItem, SelectItemQualifiedWildcardKind, SetExpr, UnaryOperator, Value,
+};
+use std::fmt::Display;
+
+use super::{Catalog, ScopeTable};
+use crate::lang::php::{Field, Kind, from_sql_value, php_string};
+use crate::sql::ir::SqlType;
+
+/// A result column: its key in the fetched row, PHP type and nullability.
+#[derive(Debug, Clone)]
+struct Column {
+    name: String,
+    kind: Kind,
+    nullable: bool,
+    json: bool,
+    /// The column type as written in the schema, when the value comes
+    /// straight from a column.
+    sql_type: Option<String>,
+}
+
+impl Column {
+    fn from_field(field: &Field, nullable: bool) -> Self {
+        Self {
+            name: field.name.clone(),
+            kind: field.type_annotation,
+            nullable: field.nullable || nullable,
+            json: field.is_json(),
+            sql_type: Some(field.sql_type.clone()),
+        }
+    }
+
+    fn typed(kind: Kind, nullable: bool) -> Self {
+        Self {
+            name: String::new(),
+            kind,
+            nullable,
+            json: false,
+            sql_type: None,
+        }
+    }
+
+    /// What nothing is known about.
+    fn unknown() -> Self {
+        Self::typed(Kind::Mixed, true)
+    }
+
+    fn nullable(mut self, nullable: bool) -> Self {
+        self.nullable |= nullable;
+        self
+    }
+
+    /// The promoted constructor parameter. `mixed` already includes null.
+    fn parameter(&self) -> String {
+        let null = match self.kind {
+            Kind::Mixed => "",
+            _ if self.nullable => "?",
+            _ => "",
+        };
+        format!("public readonly {null}{} ${}", self.kind, self.name)
+    }
+}
+
+/// The tables a projection can refer to.
+pub struct Scope<'c, 'a> {
+    pub catalog: &'c Catalog<'a>,
+    pub tables: Vec<ScopeTable<'a>>,
+}
+
+/// A class holding one row of a query whose columns don't match a model.
+#[derive(Debug)]
+pub struct Row {
+    pub class_name: String,
+    columns: Vec<Column>,
+}
+
+impl Row {
+    /// Infers the columns `projection` produces from the tables in `scope`.
+    /// Returns `None` when a wildcard covers a table the schema doesn't
+    /// describe, since its columns can't be listed.
+    pub fn new(
+        class_name: String,
+        projection: &[SelectItem],
+        scope: &Scope,
+        complete: bool,
+    ) -> anyhow::Result<Option<Self>> {
+        let mut columns: Vec<Column> = vec![];
+
+        for item in projection {
+            let mut item_columns = match item {
+                SelectItem::UnnamedExpr(expr) => {
+                    let name = match expr {
+                        Expr::Identifier(ident) => ident.value.clone(),
+                        Expr::CompoundIdentifier(idents) => match idents.last() {
+                            Some(ident) => ident.value.clone(),
+                            None => continue,
+                        },
+                        _ => {
+                            return Err(anyhow!(
+                                "result column `{expr}` needs an alias, e.g. `{expr} AS name`"
+                            ));
+                        }
+                    };
+                    vec![Column {
+                        name,
+                        ..infer(expr, scope)
+                    }]
+                }
+                SelectItem::ExprWithAlias { expr, alias } => vec![Column {
+                    name: alias.value.clone(),
+                    ..infer(expr, scope)
+                }],
+                SelectItem::Wildcard(_) => {
+                    if !complete {
+                        return Ok(None);
+                    }
+                    scope
+                        .tables
+                        .iter()
+                        .flat_map(|table| {
+                            table
+                                .class
+                                .fields
+                                .iter()
+                                .map(|field| Column::from_field(field, table.nullable))
+                        })
+                        .collect()
+                }
+                SelectItem::QualifiedWildcard(
+                    SelectItemQualifiedWildcardKind::ObjectName(name),
+                    _,
+                ) => {
+                    let alias = name.0.last().and_then(|part| part.as_ident());
+                    let Some(table) = scope
+                        .tables
+                        .iter()
+                        .find(|table| alias.is_some_and(|alias| alias.value == table.name))
+                    else {
+                        return Ok(None);
+                    };
+                    table
+                        .class
+                        .fields
+                        .iter()
+                        .map(|field| Column::from_field(field, table.nullable))
+                        .collect()
+                }
+                SelectItem::QualifiedWildcard(..) => return Ok(None),
+            };
+
+            for column in &item_columns {
+                if columns.iter().any(|c| c.name == column.name) {
+                    return Err(anyhow!(
+                        "result column `{}` appears more than once; alias one of them",
+                        column.name
+                    ));
+                }
+                if !is_php_identifier(&column.name) {
+                    return Err(anyhow!(
+                        "result column `{}` isn't a valid PHP property name; alias it",
+                        column.name
+                    ));
+                }
+            }
+            columns.append(&mut item_columns);
+        }
+
+        Ok(Some(Self {
+            class_name,
+            columns,
+        }))
+    }
+}
+
+impl Display for Row {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parameters = String::new();
+        for (i, column) in self.columns.iter().enumerate() {
+            if let Some(sql_type) = &column.sql_type {
+                let null = if column.nullable { "|null" } else { "" };
+                parameters.push_str(&format!(
+                    "        /** @var {}{null} {sql_type} */\n",
+                    column.kind
+                ));
+            }
+            let comma = if i + 1 == self.columns.len() { "" } else { "," };
+            parameters.push_str(&format!("        {}{comma}\n", column.parameter()));
+        }
+
+        let args: String = self
+            .columns
+            .iter()
+            .map(|column| {
+                format!(
+                    "            {}: {},\n",
+                    column.name,
+                    from_sql_value(
+                        &column.kind,
+                        column.json,
+                        column.nullable,
+                        &format!("$row[{}]", php_string(&column.name))
+                    )
+                )
+            })
+            .collect();
+
+        write!(
+            f,
+            "class {} {{\n    public function __construct(\n{parameters}    ) {{ }}\n\n    /** @param array<string, mixed> $row */\n    public static function fromRow(array $row): self {{\n        return new self(\n{args}        );\n    }}\n}}",
+            self.class_name
+        )
+    }
+}
+
+fn is_php_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c == '_' || c.is_ascii_alphabetic())
+        && chars.all(|c| c == '_' || c.is_ascii_alphanumeric())
+}
+
+/// Infers the type and nullability of `expr`. The returned column has no name.
+fn infer(expr: &Expr, scope: &Scope) -> Column {
+    match expr {
+        Expr::Identifier(ident) => resolve(std::slice::from_ref(ident), scope),
+        Expr::CompoundIdentifier(idents) => resolve(idents, scope),
+        Expr::Nested(expr) => infer(expr, scope),
+        Expr::Value(v) => match &v.value {
+            Value::Number(n, _) if n.contains(['.', 'e', 'E']) => Column::typed(Kind::Float, false),
+            Value::Number(..) => Column::typed(Kind::Int, false),
+            Value::SingleQuotedString(_)
+            | Value::DoubleQuotedString(_)
+            | Value::NationalStringLiteral(_)
+            | Value::EscapedStringLiteral(_) => Column::typed(Kind::String, false),
+            Value::Boolean(_) => Column::typed(Kind::Bool, false),
+            _ => Column::unknown(),
+        },
+        Expr::Cast {
+            kind,
+            expr,
+            data_type,
+            ..
+        } => {
+            // TRY_CAST and SAFE_CAST yield NULL instead of failing.
+            let fallible = matches!(kind, CastKind::TryCast | CastKind::SafeCast);
+            let inner = infer(expr, scope);
+            Column {
+                kind: Kind::from(&SqlType::from(data_type)),
+                json: data_type.to_string().to_uppercase().starts_with("JSON"),
+                sql_type: Some(data_type.to_string()),
+                ..inner.nullable(fallible)
+            }
+        }
+        Expr::UnaryOp { op, expr } => {
+            let inner = infer(expr, scope);
+            match op {
+                UnaryOperator::Not => Column::typed(Kind::Bool, inner.nullable),
+                _ => Column {
+                    sql_type: None,
+                    ..inner
+                },
+            }
+        }
+        Expr::BinaryOp { left, op, right } => {
+            let (left, right) = (infer(left, scope), infer(right, scope));
+            let nullable = left.nullable || right.nullable;
+            let kind = match op {
+                BinaryOperator::Plus
+                | BinaryOperator::Minus
+                | BinaryOperator::Multiply
+                | BinaryOperator::Modulo => match (left.kind, right.kind) {
+                    (Kind::Int, Kind::Int) => Kind::Int,
+                    (Kind::Int | Kind::Float, Kind::Int | Kind::Float) => Kind::Float,
+                    _ => Kind::Mixed,
+                },
+                BinaryOperator::Divide => Kind::Float,
+                BinaryOperator::StringConcat => Kind::String,
+                BinaryOperator::Gt
+                | BinaryOperator::Lt
+                | BinaryOperator::GtEq
+                | BinaryOperator::LtEq
+                | BinaryOperator::Eq
+                | BinaryOperator::NotEq
+                | BinaryOperator::And
+                | BinaryOperator::Or => Kind::Bool,
+                _ => Kind::Mixed,
+            };
+            Column::typed(kind, nullable)
+        }
+        Expr::IsNull(_)
+        | Expr::IsNotNull(_)
+        | Expr::IsTrue(_)
+        | Expr::IsFalse(_)
+        | Expr::IsNotTrue(_)
+        | Expr::IsNotFalse(_)
+        | Expr::IsDistinctFrom(..)
+        | Expr::IsNotDistinctFrom(..)
+        | Expr::Exists { .. } => Column::typed(Kind::Bool, false),
+        Expr::Between {
+            expr, low, high, ..
+        } => Column::typed(
+            Kind::Bool,
+            [expr, low, high]
+                .iter()
+                .any(|expr| infer(expr, scope).nullable),
+        ),
+        Expr::InList { expr, .. }
+        | Expr::InSubquery { expr, .. }
+        | Expr::Like { expr, .. }
+        | Expr::ILike { expr, .. } => Column::typed(Kind::Bool, infer(expr, scope).nullable),
+        Expr::Case {
+            conditions,
+            else_result,
+            ..
+        } => {
+            let results: Vec<Column> = conditions
+                .iter()
+                .map(|when| &when.result)
+                .chain(else_result.as_deref())
+                .map(|expr| infer(expr, scope))
+                .collect();
+            // Without ELSE, a CASE that matches nothing is NULL.
+            let nullable = else_result.is_none() || results.iter().any(|result| result.nullable);
+            match results.first() {
+                Some(first) if results.iter().all(|result| same_kind(result, first)) => {
+                    Column::typed(first.kind, nullable)
+                }
+                _ => Column::typed(Kind::Mixed, nullable),
+            }
+        }
+        // A scalar subquery is NULL when it finds no row.
+        Expr::Subquery(query) => match query.body.as_ref() {
+            SetExpr::Select(select) => match select.projection.as_slice() {
+                [SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. }] => {
+                    // Correlated subqueries can see the outer tables too.
+                    let mut tables = scope.catalog.scope(&select.from);
+                    tables.extend(scope.tables.iter().cloned());
+                    let scope = Scope {
+                        catalog: scope.catalog,
+                        tables,
+                    };
+                    Column {
+                        sql_type: None,
+                        ..infer(expr, &scope).nullable(true)
+                    }
+                }
+                _ => Column::unknown(),
+            },
+            _ => Column::unknown(),
+        },
+        Expr::Function(function) => infer_function(function, scope),
+        _ => Column::unknown(),
+    }
+}
+
+fn same_kind(a: &Column, b: &Column) -> bool {
+    std::mem::discriminant(&a.kind) == std::mem::discriminant(&b.kind)
+}
+
+fn infer_function(function: &Function, scope: &Scope) -> Column {
+    let args: Vec<Column> = match &function.args {
+        FunctionArguments::List(list) => list
+            .args
+            .iter()
+            .map(|arg| match arg {
+                FunctionArg::Unnamed(FunctionArgExpr::Expr(expr))
+                | FunctionArg::Named {
+                    arg: FunctionArgExpr::Expr(expr),
+                    ..
+                } => infer(expr, scope),
+                _ => Column::unknown(),
+            })
+            .collect(),
+        _ => vec![],
+    };
+    let any_nullable = args.iter().any(|arg| arg.nullable);
+    let first = args.first().cloned().unwrap_or_else(Column::unknown);
+
+    let name = function.name.to_string().to_lowercase();
+    match name.as_str() {
+        "count" => Column::typed(Kind::Int, false),
+        // Aggregates over no rows are NULL.
+        "sum" | "total" => match first.kind {
+            Kind::Int | Kind::Float => Column::typed(first.kind, true),
+            _ => Column::typed(Kind::Mixed, true),
+        },
+        "avg" => Column::typed(Kind::Float, true),
+        "min" | "max" => Column {
+            sql_type: None,
+            ..first.nullable(true)
+        },
+        // NULL only when every argument is.
+        "coalesce" | "ifnull" | "nvl" | "isnull" => Column {
+            sql_type: None,
+            nullable: args.iter().all(|arg| arg.nullable),
+            ..first
+        },
+        "nullif" => Column {
+            sql_type: None,
+            ..first.nullable(true)
+        },
+        "lower" | "upper" | "trim" | "ltrim" | "rtrim" | "concat" | "substr" | "substring"
+        | "replace" | "left" | "right" | "lpad" | "rpad" => {
+            Column::typed(Kind::String, any_nullable)
+        }
+        "length" | "char_length" | "character_length" | "octet_length" | "len" => {
+            Column::typed(Kind::Int, any_nullable)
+        }
+        "abs" | "round" | "floor" | "ceil" | "ceiling" => match first.kind {
+            Kind::Int | Kind::Float => Column::typed(first.kind, any_nullable),
+            _ => Column::typed(Kind::Mixed, true),
+        },
+        "now" | "current_timestamp" | "localtimestamp" | "getdate" => {
+            Column::typed(Kind::DateTime, false)
+        }
+        _ => Column::unknown(),
+    }
+}
+
+/// Resolves a possibly qualified column reference. Columns of tables on the
+/// optional side of an outer join are nullable.
+fn resolve(idents: &[Ident], scope: &Scope) -> Column {
+    let Some((column, qualifier)) = idents.split_last() else {
+        return Column::unknown();
+    };
+    let qualifier = qualifier.last().map(|ident| ident.value.as_str());
+
+    scope
+        .tables
+        .iter()
+        .filter(|table| qualifier.is_none_or(|q| q == table.name))
+        .find_map(|table| {
+            table
+                .class
+                .fields
+                .iter()
+                .find(|field| field.name == column.value)
+                .map(|field| Column::from_field(field, table.nullable))
+        })
+        .unwrap_or_else(Column::unknown)
+}
@@ -0,0 +1,245 @@
+use std::fmt::Display;
+
+use super::{Class, Field, php_string};
+use crate::sql::dialect::Dialect;
+
+/// A PDO-backed data access class for a model.
+pub struct Repository<'a> {
+    class: &'a Class,
+    dialect: Dialect,
+}
+
+impl<'a> Repository<'a> {
+    pub fn new(class: &'a Class, dialect: Dialect) -> Self {
+        Self { class, dialect }
+    }
+
+    pub fn class_name(&self) -> String {
+        format!("{}Repository", self.class.class_name)
+    }
+
+    fn primary_key(&self) -> Vec<&Field> {
+        self.class
+            .primary_key
+            .iter()
+            .filter_map(|key| self.class.fields.iter().find(|field| &field.name == key))
+            .collect()
+    }
+
+    /// The PHP function that quotes identifiers at runtime, for columns that
+    /// are only known when the query is built.
+    fn quote_function(&self) -> String {
+        let body = match self.dialect {
+            Dialect::MySql => "'`' . str_replace('`', '``', $identifier) . '`'",
+            Dialect::MsSql => "'[' . str_replace(']', ']]', $identifier) . ']'",
+            Dialect::Generic | Dialect::Postgres | Dialect::Sqlite => {
+                "'\"' . str_replace('\"', '\"\"', $identifier) . '\"'"
+            }
+        };
+
+        format!(
+            "    private static function quote(string $identifier): string {{\n        return {body};\n    }}\n"
+        )
+    }
+
+    /// `WHERE` clause matching the primary key against `:pk__{column}`
+    /// placeholders.
+    fn key_condition(&self, key: &[&Field]) -> String {
+        key.iter()
+            .map(|field| {
+                format!(
+                    "{} = :pk__{}",
+                    self.dialect.quote_ident(&field.name),
+                    field.name
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(" AND ")
+    }
+
+    fn key_params(key: &[&Field]) -> String {
+        key.iter()
+            .map(|field| format!("{} ${}", field.type_annotation, field.name))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn key_bindings(key: &[&Field], value: impl Fn(&Field) -> String) -> String {
+        key.iter()
+            .map(|field| format!("'pk__{}' => {}", field.name, value(field)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn find(&self, key: &[&Field]) -> String {
+        let sql = php_string(&format!(" WHERE {}", self.key_condition(key)));
+        format!(
+            "    public function find({}): ?{model} {{\n        $stmt = $this->pdo->prepare('SELECT * FROM ' . self::TABLE . {sql});\n        $stmt->execute([{}]);\n        $row = $stmt->fetch(\\PDO::FETCH_ASSOC);\n\n        return $row === false ? null : {model}::fromRow($row);\n    }}\n",
+            Self::key_params(key),
+            Self::key_bindings(key, |field| format!("${}", field.name)),
+            model = self.class.class_name,
+        )
+    }
+
+    fn find_by(&self) -> String {
+        format!(
+            r#"    /**
+     * @param array<string, mixed> $criteria Column values to match, joined with AND.
+     * @return {model}[]
+     */
+    public function findBy(array $criteria): array {{
+        $where = [];
+        $params = [];
+        foreach ($criteria as $column => $value) {{
+            if (!in_array($column, self::COLUMNS, true)) {{
+                throw new \InvalidArgumentException("Unknown column: {{$column}}");
+            }}
+            if ($value === null) {{
+                $where[] = self::quote($column) . ' IS NULL';
+            }} else {{
+                $where[] = self::quote($column) . ' = :' . $column;
+                $params[$column] = $value;
+            }}
+        }}
+
+        $sql = 'SELECT * FROM ' . self::TABLE;
+        if ($where !== []) {{
+            $sql .= ' WHERE ' . implode(' AND ', $where);
+        }}
+
+        $stmt = $this->pdo->prepare($sql);
+        $stmt->execute($params);
+
+        return array_map(
+            fn (array $row): {model} => {model}::fromRow($row),
+            $stmt->fetchAll(\PDO::FETCH_ASSOC),
+        );
+    }}
+"#,
+            model = self.class.class_name,
+        )
+    }
+
+    fn insert(&self, key: &[&Field]) -> String {
+        let (returning, result) = match key {
+            // Postgres has no usable lastInsertId() without a sequence name,
+            // so the key is read back with RETURNING instead.
+            [field] if field.generated => match self.dialect {
+                Dialect::Postgres => (
+                    format!(" RETURNING {}", self.dialect.quote_ident(&field.name)),
+                    Some((String::from("$stmt->fetchColumn()"), String::from("mixed"))),
+                ),
+                _ => (
+                    String::new(),
+                    Some((
+                        String::from("$this->pdo->lastInsertId()"),
+                        String::from("mixed"),
+                    )),
+                ),
+            },
+            // The caller supplied the key.
+            [field] => (
+                String::new(),
+                Some((field.accessor("$model"), field.type_annotation.to_string())),
+            ),
+            _ => (String::new(), None),
+        };
+        let close = php_string(&format!("){returning}"));
+        let (doc, return_type, result) = match result {
+            Some((result, return_type)) => (
+                format!("    /** @return {return_type} The primary key of the inserted row. */\n"),
+                return_type,
+                format!("\n\n        return {result};"),
+            ),
+            None => (String::new(), String::from("void"), String::new()),
+        };
+
+        format!(
+            r#"{doc}    public function insert({model} $model): {return_type} {{
+        $row = $model->toInsertArray();
+        $columns = array_map(fn (string $column): string => self::quote($column), array_keys($row));
+        $placeholders = array_map(fn (string $column): string => ':' . $column, array_keys($row));
+
+        $stmt = $this->pdo->prepare(
+            'INSERT INTO ' . self::TABLE . ' (' . implode(', ', $columns) . ') VALUES (' . implode(', ', $placeholders) . {close}
+        );
+        $stmt->execute($row);{result}
+    }}
+"#,
+            model = self.class.class_name,
+        )
+    }
+
+    fn update(&self, key: &[&Field]) -> String {
+        let sql = php_string(&format!(" WHERE {}", self.key_condition(key)));
+        format!(
+            r#"    public function update({model} $model): bool {{
+        $row = $model->toInsertArray();
+        $sets = array_map(fn (string $column): string => self::quote($column) . ' = :' . $column, array_keys($row));
+
+        $stmt = $this->pdo->prepare('UPDATE ' . self::TABLE . ' SET ' . implode(', ', $sets) . {sql});
+
+        return $stmt->execute($row + [{}]);
+    }}
+"#,
+            Self::key_bindings(key, |field| field.accessor("$model")),
+            model = self.class.class_name,
+        )
+    }
+
+    fn delete(&self, key: &[&Field]) -> String {
+        let sql = php_string(&format!(" WHERE {}", self.key_condition(key)));
+        format!(
+            "    public function delete({}): bool {{\n        $stmt = $this->pdo->prepare('DELETE FROM ' . self::TABLE . {sql});\n\n        return $stmt->execute([{}]);\n    }}\n",
+            Self::key_params(key),
+            Self::key_bindings(key, |field| format!("${}", field.name)),
+        )
+    }
+}
+
+impl Display for Repository<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut table = self.class.schema.clone();
+        table.push(self.class.name.clone());
+
+        let columns = self
+            .class
+            .fields
+            .iter()
+            .map(|field| php_string(&field.name))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let key = self.primary_key();
+        let mut methods = vec![];
+        if !key.is_empty() {
+            methods.push(self.find(&key));
+        }
+        methods.push(self.find_by());
+        methods.push(self.insert(&key));
+        if !key.is_empty() {
+            methods.push(self.update(&key));
+            methods.push(self.delete(&key));
+        }
+        methods.push(self.quote_function());
+
+        write!(
+            f,
+            "class {} {{\n    private const TABLE = {};\n    private const COLUMNS = [{columns}];\n\n    public function __construct(private \\PDO $pdo) {{ }}\n\n{}}}",
+            self.class_name(),
+            php_string(&self.dialect.quote_qualified(&table)),
+            methods.join("\n")
+        )
+    }
+}
+
+impl Field {
+    /// Reads this field from the model held in `var`, going through the
+    /// generated getter when the property is private.
+    fn accessor(&self, var: &str) -> String {
+        match self.visibility {
+            super::Visibilty::Public => format!("{var}->{}", self.name),
+            _ => format!("{var}->{}()", self.getter_name()),
+        }
+    }
+}
@@ -0,0 +1,331 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::cli::config::{Config, PythonStyle, SchemaMode};
+use crate::lang::{Backend, Model, PascalCase, SnakeCase, string_literal};
+use crate::sql::ir::{Column, SqlType};
+
+const KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+/// Generates a `dataclass` or Pydantic model per table, plus an `Enum` per
+/// `ENUM` column.
+pub struct Python {
+    schema_mode: SchemaMode,
+    style: PythonStyle,
+}
+
+/// What a model's classes need: names imported per module, and the enums
+/// they declare.
+#[derive(Default)]
+struct Definitions {
+    imports: BTreeMap<&'static str, BTreeSet<&'static str>>,
+    enums: Vec<(String, Vec<String>)>,
+}
+
+impl Definitions {
+    fn import(&mut self, module: &'static str, name: &'static str) {
+        self.imports.entry(module).or_default().insert(name);
+    }
+}
+
+impl Python {
+    pub fn new(config: &Config) -> anyhow::Result<Self> {
+        Ok(Self {
+            schema_mode: SchemaMode::from_str(&config.schema_mode)?,
+            style: PythonStyle::from_str(&config.python.style)?,
+        })
+    }
+
+    /// The annotation of each column, recording what it needs in `defs`.
+    fn annotations(&self, model: &Model, defs: &mut Definitions) -> Vec<String> {
+        match self.style {
+            PythonStyle::Dataclass => defs.import("dataclasses", "dataclass"),
+            PythonStyle::Pydantic => defs.import("pydantic", "BaseModel"),
+        }
+
+        model
+            .table
+            .columns
+            .iter()
+            .map(|column| {
+                let enum_name = format!("{}{}", model.class_name, column.name.to_pascal_case());
+                let annotation = type_of(&column.sql_type, &enum_name, defs);
+                match column.nullable {
+                    true => {
+                        defs.import("typing", "Optional");
+                        format!("Optional[{annotation}]")
+                    }
+                    false => annotation,
+                }
+            })
+            .collect()
+    }
+
+    /// The default of a field whose attribute name differs from its column.
+    fn alias(&self, column: &str, defs: &mut Definitions) -> String {
+        match self.style {
+            PythonStyle::Dataclass => {
+                defs.import("dataclasses", "field");
+                format!(" = field(metadata={{\"column\": {}}})", py_string(column))
+            }
+            PythonStyle::Pydantic => {
+                defs.import("pydantic", "Field");
+                format!(" = Field(alias={})", py_string(column))
+            }
+        }
+    }
+}
+
+/// The annotation for `sql_type`. `ENUM`s are declared as `enum_name`.
+fn type_of(sql_type: &SqlType, enum_name: &str, defs: &mut Definitions) -> String {
+    let name = match sql_type {
+        SqlType::Integer { .. } => "int",
+        SqlType::Float { .. } => "float",
+        SqlType::Decimal { .. } => {
+            defs.import("decimal", "Decimal");
+            "Decimal"
+        }
+        SqlType::Boolean => "bool",
+        SqlType::Char { .. } | SqlType::VarChar { .. } | SqlType::Text => "str",
+        SqlType::Binary { .. } => "bytes",
+        SqlType::Date => {
+            defs.import("datetime", "date");
+            "date"
+        }
+        SqlType::Time => {
+            defs.import("datetime", "time");
+            "time"
+        }
+        SqlType::Timestamp { .. } => {
+            defs.import("datetime", "datetime");
+            "datetime"
+        }
+        SqlType::Interval => {
+            defs.import("datetime", "timedelta");
+            "timedelta"
+        }
+        SqlType::Uuid => {
+            defs.import("uuid", "UUID");
+            "UUID"
+        }
+        SqlType::Json | SqlType::Other { .. } => {
+            defs.import("typing", "Any");
+            "Any"
+        }
+        SqlType::Array { element } => {
+            return format!("list[{}]", type_of(element, enum_name, defs));
+        }
+        SqlType::Enum { values } => {
+            defs.import("enum", "Enum");
+            if !defs.enums.iter().any(|(name, _)| name == enum_name) {
+                defs.enums.push((enum_name.to_string(), values.clone()));
+            }
+            return enum_name.to_string();
+        }
+    };
+
+    name.to_string()
+}
+
+impl Backend for Python {
+    fn file_name(&self, model: &Model) -> PathBuf {
+        let mut path: PathBuf = match self.schema_mode {
+            SchemaMode::Namespace => model
+                .table
+                .schema
+                .iter()
+                .map(|part| part.to_snake_case())
+                .collect(),
+            SchemaMode::Prefix => PathBuf::new(),
+        };
+        path.push(format!("{}.py", model.stem));
+
+        path
+    }
+
+    fn preamble(&self, model: &Model) -> String {
+        let mut defs = Definitions::default();
+        self.annotations(model, &mut defs);
+        for column in &model.table.columns {
+            if attribute_name(&column.name) != column.name {
+                self.alias(&column.name, &mut defs);
+            }
+        }
+
+        // Standard library imports come first, as isort orders them.
+        let (third_party, standard): (Vec<_>, Vec<_>) = defs
+            .imports
+            .iter()
+            .partition(|(module, _)| **module == "pydantic");
+        let group = |imports: Vec<(&&str, &BTreeSet<&str>)>| -> String {
+            imports
+                .into_iter()
+                .map(|(module, names)| {
+                    let names: Vec<&str> = names.iter().copied().collect();
+                    format!("from {module} import {}\n", names.join(", "))
+                })
+                .collect()
+        };
+
+        let mut imports = group(standard);
+        if !third_party.is_empty() {
+            if !imports.is_empty() {
+                imports.push('\n');
+            }
+            imports.push_str(&group(third_party));
+        }
+
+        format!(
+            "# Generated by quaff from {}.sql.\n\n{imports}\n\n",
+            model.stem
+        )
+    }
+
+    fn type_name(&self, column: &Column) -> String {
+        type_of(&column.sql_type, "Enum", &mut Definitions::default())
+    }
+
+    fn render(&self, model: &Model) -> anyhow::Result<String> {
+        let mut defs = Definitions::default();
+        let annotations = self.annotations(model, &mut defs);
+
+        let mut output = String::new();
+        for (name, values) in &defs.enums {
+            output.push_str(&format!("class {name}(str, Enum):\n"));
+            if values.is_empty() {
+                output.push_str("    pass\n");
+            }
+            for value in values {
+                output.push_str(&format!(
+                    "    {} = {}\n",
+                    member_name(value),
+                    py_string(value)
+                ));
+            }
+            output.push_str("\n\n");
+        }
+
+        let mut body = match &model.table.comment {
+            Some(comment) => format!("{}\n", docstring(comment)),
+            None => String::new(),
+        };
+        for (column, annotation) in model.table.columns.iter().zip(&annotations) {
+            if let Some(comment) = &column.comment {
+                for line in comment.lines() {
+                    match line.trim_end() {
+                        "" => body.push_str("    #\n"),
+                        line => body.push_str(&format!("    # {line}\n")),
+                    }
+                }
+            }
+
+            let name = attribute_name(&column.name);
+            let alias = match name == column.name {
+                true => String::new(),
+                false => self.alias(&column.name, &mut defs),
+            };
+            body.push_str(&format!("    {name}: {annotation}{alias}\n"));
+        }
+        if model.table.columns.is_empty() && model.table.comment.is_none() {
+            body.push_str("    pass\n");
+        }
+
+        match self.style {
+            PythonStyle::Dataclass => {
+                output.push_str(&format!("@dataclass\nclass {}:\n{body}", model.class_name))
+            }
+            PythonStyle::Pydantic => {
+                output.push_str(&format!("class {}(BaseModel):\n{body}", model.class_name))
+            }
+        }
+
+        Ok(output)
+    }
+}
+
+/// The attribute for `column`: snake case, and not a keyword.
+fn attribute_name(column: &str) -> String {
+    let name = column.to_snake_case();
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("column_{name}")
+    } else if KEYWORDS.contains(&name.as_str()) {
+        format!("{name}_")
+    } else {
+        name
+    }
+}
+
+/// The `Enum` member for `value`, e.g. `IN_REVIEW` for `in review`.
+fn member_name(value: &str) -> String {
+    let name = value.to_snake_case().to_uppercase();
+    match name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        true => name,
+        false => format!("VALUE_{name}"),
+    }
+}
+
+/// Renders `comment` as a class docstring.
+fn docstring(comment: &str) -> String {
+    let comment = comment
+        .replace('\\', "\\\\")
+        .replace("\"\"\"", "\\\"\\\"\\\"");
+    let lines: Vec<&str> = comment.lines().map(str::trim_end).collect();
+
+    if let [line] = lines.as_slice() {
+        return format!("    \"\"\"{line}\"\"\"\n");
+    }
+
+    let mut doc = String::from("    \"\"\"\n");
+    for line in lines {
+        if line.is_empty() {
+            doc.push('\n');
+        } else {
+            doc.push_str(&format!("    {line}\n"));
+        }
+    }
+    doc.push_str("    \"\"\"\n");
+
+    doc
+}
+
+/// Quotes `s` as a double-quoted Python string literal.
+fn py_string(s: &str) -> String {
+    string_literal(s, '"')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lang::testing::render;
+
+    #[test]
+    fn attribute_names() {
+        assert_eq!(attribute_name("createdAt"), "created_at");
+        assert_eq!(attribute_name("class"), "class_");
+        assert_eq!(attribute_name("from"), "from_");
+        assert_eq!(attribute_name("2fa"), "column_2fa");
+    }
+
+    #[test]
+    fn member_names() {
+        assert_eq!(member_name("in review"), "IN_REVIEW");
+        assert_eq!(member_name("1st"), "VALUE_1ST");
+    }
+
+    #[test]
+    fn aliases() {
+        let sql = "CREATE TABLE t (\"from\" TEXT NOT NULL)";
+
+        let code = render("lang = \"python\"", sql);
+        assert!(code.contains("    from_: str = field(metadata={\"column\": \"from\"})\n"));
+
+        let code = render("lang = \"python\"\n[python]\nstyle = \"pydantic\"", sql);
+        assert!(code.contains("    from_: str = Field(alias=\"from\")\n"));
+    }
+}
@@ -0,0 +1,259 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::cli::config::{Config, DateCrate, SchemaMode};
+use crate::lang::{Backend, CommentStyle, File, Model, SnakeCase, string_literal};
+use crate::sql::ir::{Column, SqlType};
+use crate::sql::query::NamedQuery;
+
+/// Generates one struct per table, describing its rows, plus the `mod.rs`
+/// files tying them together.
+pub struct Rust {
+    schema_mode: SchemaMode,
+    dates: DateCrate,
+    serde: bool,
+    sqlx: bool,
+}
+
+impl Rust {
+    pub fn new(config: &Config) -> anyhow::Result<Self> {
+        Ok(Self {
+            schema_mode: SchemaMode::from_str(&config.schema_mode)?,
+            dates: DateCrate::from_str(&config.rust.dates)?,
+            serde: config.rust.serde,
+            sqlx: config.rust.sqlx,
+        })
+    }
+
+    /// The module path of `model` below the output directory, without the
+    /// model's own module.
+    fn modules(&self, model: &Model) -> Vec<String> {
+        match self.schema_mode {
+            SchemaMode::Namespace => model
+                .table
+                .schema
+                .iter()
+                .map(|part| part.to_snake_case())
+                .collect(),
+            SchemaMode::Prefix => vec![],
+        }
+    }
+
+    fn type_of(&self, sql_type: &SqlType) -> String {
+        let chrono = matches!(self.dates, DateCrate::Chrono);
+        let name = match sql_type {
+            SqlType::Integer { bytes, unsigned } => match (bytes, unsigned) {
+                (1, false) => "i8",
+                (1, true) => "u8",
+                (2, false) => "i16",
+                (2, true) => "u16",
+                (4, false) => "i32",
+                (4, true) => "u32",
+                (8, false) => "i64",
+                (8, true) => "u64",
+                (16, false) => "i128",
+                (16, true) => "u128",
+                // Wider than any primitive.
+                _ => "String",
+            },
+            SqlType::Float { double: true } => "f64",
+            SqlType::Float { double: false } => "f32",
+            SqlType::Decimal { .. } => "rust_decimal::Decimal",
+            SqlType::Boolean => "bool",
+            SqlType::Char { .. }
+            | SqlType::VarChar { .. }
+            | SqlType::Text
+            | SqlType::Interval
+            | SqlType::Enum { .. }
+            | SqlType::Other { .. } => "String",
+            SqlType::Binary { .. } => "Vec<u8>",
+            SqlType::Date if chrono => "chrono::NaiveDate",
+            SqlType::Date => "time::Date",
+            SqlType::Time if chrono => "chrono::NaiveTime",
+            SqlType::Time => "time::Time",
+            SqlType::Timestamp { time_zone: true } if chrono => "chrono::DateTime<chrono::Utc>",
+            SqlType::Timestamp { time_zone: true } => "time::OffsetDateTime",
+            SqlType::Timestamp { time_zone: false } if chrono => "chrono::NaiveDateTime",
+            SqlType::Timestamp { time_zone: false } => "time::PrimitiveDateTime",
+            SqlType::Json => "serde_json::Value",
+            SqlType::Uuid => "uuid::Uuid",
+            SqlType::Array { element } => return format!("Vec<{}>", self.type_of(element)),
+        };
+
+        name.to_string()
+    }
+
+    fn derives(&self) -> String {
+        let mut derives = vec!["Debug", "Clone"];
+        if self.serde {
+            derives.extend(["serde::Serialize", "serde::Deserialize"]);
+        }
+        if self.sqlx {
+            derives.push("sqlx::FromRow");
+        }
+
+        format!("#[derive({})]\n", derives.join(", "))
+    }
+
+    /// The `rename` attributes mapping `field` back to `column`, when they
+    /// differ.
+    fn renames(&self, field: &str, column: &str) -> String {
+        if field.trim_start_matches("r#") == column {
+            return String::new();
+        }
+
+        let column = rust_string(column);
+        let mut attributes = String::new();
+        if self.serde {
+            attributes.push_str(&format!("    #[serde(rename = {column})]\n"));
+        }
+        if self.sqlx {
+            attributes.push_str(&format!("    #[sqlx(rename = {column})]\n"));
+        }
+
+        attributes
+    }
+}
+
+impl Backend for Rust {
+    fn file_name(&self, model: &Model) -> PathBuf {
+        let mut path: PathBuf = self.modules(model).into_iter().collect();
+        path.push(format!("{}.rs", model.stem));
+
+        path
+    }
+
+    fn preamble(&self, model: &Model) -> String {
+        format!("// Generated by quaff from {}.sql.\n\n", model.stem)
+    }
+
+    fn type_name(&self, column: &Column) -> String {
+        self.type_of(&column.sql_type)
+    }
+
+    fn render(&self, model: &Model) -> anyhow::Result<String> {
+        let mut fields = String::new();
+        for column in &model.table.columns {
+            if let Some(comment) = &column.comment {
+                fields.push_str(&CommentStyle::lines("///").render(comment.lines(), "    "));
+            }
+
+            let name = identifier(&column.name.to_snake_case());
+            let type_name = match column.nullable {
+                true => format!("Option<{}>", self.type_of(&column.sql_type)),
+                false => self.type_of(&column.sql_type),
+            };
+            fields.push_str(&self.renames(&name, &column.name));
+            fields.push_str(&format!("    pub {name}: {type_name},\n"));
+        }
+
+        let doc = match &model.table.comment {
+            Some(comment) => CommentStyle::lines("///").render(comment.lines(), ""),
+            None => String::new(),
+        };
+
+        Ok(format!(
+            "{doc}{}pub struct {} {{\n{fields}}}\n",
+            self.derives(),
+            model.class_name
+        ))
+    }
+
+    /// Writes a `mod.rs` declaring the modules in each generated directory.
+    fn extra_files(
+        &self,
+        models: &[Model],
+        _queries: &[(String, Vec<NamedQuery>)],
+    ) -> anyhow::Result<Vec<File>> {
+        // The file of each module, relative to its directory, by the name
+        // the module is derived from.
+        let mut modules: BTreeMap<PathBuf, BTreeMap<String, String>> = BTreeMap::new();
+        for model in models {
+            let path = self.modules(model);
+            for (i, module) in path.iter().enumerate() {
+                let dir: PathBuf = path[..i].iter().collect();
+                modules
+                    .entry(dir)
+                    .or_default()
+                    .insert(module.clone(), format!("{module}/mod.rs"));
+            }
+
+            let dir: PathBuf = path.into_iter().collect();
+            modules
+                .entry(dir)
+                .or_default()
+                .insert(model.stem.clone(), format!("{}.rs", model.stem));
+        }
+
+        Ok(modules
+            .into_iter()
+            .map(|(dir, modules)| {
+                let mut contents = String::from("// Generated by quaff.\n\n");
+                for (name, file) in modules {
+                    let module = identifier(&name);
+                    // Rust looks for a module's file by its name, so files
+                    // named otherwise, e.g. `2fa-codes.rs`, are pointed to.
+                    if module.trim_start_matches("r#") != name {
+                        contents.push_str(&format!("#[path = {}]\n", rust_string(&file)));
+                    }
+                    contents.push_str(&format!("pub mod {module};\n"));
+                }
+
+                File {
+                    path: dir.join("mod.rs"),
+                    contents,
+                }
+            })
+            .collect())
+    }
+}
+
+/// Makes `name` usable as a field or module name, replacing characters
+/// identifiers can't contain with `_` and escaping keywords.
+fn identifier(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do",
+        "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in",
+        "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+        "return", "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe",
+        "unsized", "use", "virtual", "where", "while", "yield",
+    ];
+
+    let name: String = name
+        .chars()
+        .map(|c| match c.is_alphanumeric() || c == '_' {
+            true => c,
+            false => '_',
+        })
+        .collect();
+
+    match name.as_str() {
+        // These can't be raw identifiers.
+        "self" | "super" | "crate" | "Self" => format!("{name}_"),
+        name if KEYWORDS.contains(&name) => format!("r#{name}"),
+        name if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) => {
+            format!("_{name}")
+        }
+        name => name.to_string(),
+    }
+}
+
+/// Quotes `s` as a Rust string literal.
+fn rust_string(s: &str) -> String {
+    string_literal(s, '"')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identifiers() {
+        assert_eq!(identifier("user_id"), "user_id");
+        assert_eq!(identifier("2fa-codes"), "_2fa_codes");
+        assert_eq!(identifier("billing.invoices"), "billing_invoices");
+        assert_eq!(identifier("type"), "r#type");
+        assert_eq!(identifier("self"), "self_");
+    }
+}
@@ -0,0 +1,107 @@
+//! Helpers for comparing generated code with the golden files in
+//! `testdata/golden/`.
+
+use std::{env, fs, path::PathBuf};
+
+use super::Model;
+use crate::cli::config::Config;
+use crate::sql::{ir::Table, query};
+
+/// The table declared by `sql`, as if it were a file in `sql/`.
+pub fn table(sql: &str) -> Table {
+    Table::new(crate::sql::parse_sql(sql).unwrap()).unwrap()
+}
+
+/// The class generated for the table declared by `sql` with `config`, a
+/// `quaff.toml`, without the file's preamble.
+pub fn render(config: &str, sql: &str) -> String {
+    let config: Config = toml::from_str(config).unwrap();
+    let table = table(sql);
+    let model = Model {
+        class_name: config.class_name(&table.schema, &table.name).unwrap(),
+        stem: table.name.clone(),
+        table,
+    };
+
+    super::backend(&config).unwrap().render(&model).unwrap()
+}
+
+/// Compares `actual` with the golden file `name`. With `QUAFF_UPDATE_GOLDEN`
+/// set, the file is rewritten instead.
+pub fn assert_golden(name: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("testdata/golden")
+        .join(name);
+    if env::var_os("QUAFF_UPDATE_GOLDEN").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path).unwrap_or_else(|err| {
+        panic!(
+            "can't read {}: {err}; run with QUAFF_UPDATE_GOLDEN=1 to create it",
+            path.display()
+        )
+    });
+    assert_eq!(expected, actual, "{} is out of date", path.display());
+}
+
+/// Generates code for the schema in `testdata/sql/` and the queries in
+/// `testdata/queries/` with `config`, a `quaff.toml`, and compares every file
+/// with its golden file under `dir`.
+fn assert_generated(dir: &str, config: &str) {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("testdata");
+    let config: Config = toml::from_str(config).unwrap();
+    let backend = super::backend(&config).unwrap();
+    let models = super::load_models(&root.join("sql"), &config).unwrap();
+    let queries = query::load_queries(&root.join("queries")).unwrap();
+
+    for file in super::generate(backend.as_ref(), &models, &queries).unwrap() {
+        assert_golden(&format!("{dir}/{}", file.path.display()), &file.contents);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::assert_generated;
+
+    /// Each golden directory and the `quaff.toml` its files are generated
+    /// with.
+    const TARGETS: &[(&str, &str)] = &[
+        (
+            "php",
+            "singularize = true\nguards = true\nrepositories = true",
+        ),
+        (
+            "eloquent",
+            "singularize = true\n[php]\nflavor = \"eloquent\"",
+        ),
+        (
+            "doctrine",
+            "singularize = true\n[php]\nflavor = \"doctrine\"",
+        ),
+        ("typescript", "lang = \"typescript\"\nsingularize = true"),
+        (
+            "rust",
+            "lang = \"rust\"\nsingularize = true\n[rust]\nserde = true\nsqlx = true",
+        ),
+        ("go", "lang = \"go\"\nsingularize = true"),
+        ("python", "lang = \"python\"\nsingularize = true"),
+        ("kotlin", "lang = \"kotlin\"\nsingularize = true"),
+        (
+            "java",
+            "lang = \"java\"\nsingularize = true\n[jvm]\njpa = true",
+        ),
+        ("csharp", "lang = \"csharp\"\nsingularize = true"),
+        ("jsonschema", "lang = \"jsonschema\"\nsingularize = true"),
+        ("openapi", "lang = \"openapi\"\nsingularize = true"),
+    ];
+
+    #[test]
+    fn goldens() {
+        for (dir, config) in TARGETS {
+            assert_generated(dir, config);
+        }
+    }
+}
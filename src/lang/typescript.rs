@@ -0,0 +1,175 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::cli::config::{Config, DateRepresentation, Declaration, SchemaMode};
+use crate::lang::{Backend, CommentStyle, Model, string_literal};
+use crate::sql::ir::{Column, SqlType};
+
+/// Generates one exported `interface` or `type` per table, describing its
+/// rows.
+pub struct TypeScript {
+    schema_mode: SchemaMode,
+    declaration: Declaration,
+    dates: DateRepresentation,
+}
+
+impl TypeScript {
+    pub fn new(config: &Config) -> anyhow::Result<Self> {
+        Ok(Self {
+            schema_mode: SchemaMode::from_str(&config.schema_mode)?,
+            declaration: Declaration::from_str(&config.typescript.declaration)?,
+            dates: DateRepresentation::from_str(&config.typescript.dates)?,
+        })
+    }
+
+    fn type_of(&self, sql_type: &SqlType) -> String {
+        match sql_type {
+            SqlType::Integer { .. } | SqlType::Float { .. } => String::from("number"),
+            // Drivers return exact numerics as strings so no precision is lost,
+            // whether or not the column declares one.
+            SqlType::Decimal { .. } => String::from("string"),
+            SqlType::Boolean => String::from("boolean"),
+            SqlType::Date | SqlType::Timestamp { .. } => match self.dates {
+                DateRepresentation::Date => String::from("Date"),
+                DateRepresentation::String => String::from("string"),
+            },
+            SqlType::Char { .. }
+            | SqlType::VarChar { .. }
+            | SqlType::Text
+            | SqlType::Binary { .. }
+            | SqlType::Time
+            | SqlType::Interval
+            | SqlType::Uuid => String::from("string"),
+            SqlType::Json | SqlType::Other { .. } => String::from("unknown"),
+            SqlType::Array { element } => match element.as_ref() {
+                SqlType::Enum { .. } => format!("({})[]", self.type_of(element)),
+                element => format!("{}[]", self.type_of(element)),
+            },
+            SqlType::Enum { values } if values.is_empty() => String::from("string"),
+            SqlType::Enum { values } => values
+                .iter()
+                .map(|value| ts_string(value))
+                .collect::<Vec<_>>()
+                .join(" | "),
+        }
+    }
+}
+
+impl Backend for TypeScript {
+    fn file_name(&self, model: &Model) -> PathBuf {
+        let dir: PathBuf = match self.schema_mode {
+            SchemaMode::Namespace => model.table.schema.iter().collect(),
+            SchemaMode::Prefix => PathBuf::new(),
+        };
+
+        dir.join(format!("{}.ts", model.stem))
+    }
+
+    fn preamble(&self, model: &Model) -> String {
+        format!("// Generated by quaff from {}.sql.\n\n", model.stem)
+    }
+
+    fn type_name(&self, column: &Column) -> String {
+        self.type_of(&column.sql_type)
+    }
+
+    fn render(&self, model: &Model) -> anyhow::Result<String> {
+        let mut properties = String::new();
+        for column in &model.table.columns {
+            if let Some(comment) = &column.comment {
+                properties.push_str(&CommentStyle::BLOCK.render(comment.lines(), "  "));
+            }
+
+            let mut type_name = self.type_of(&column.sql_type);
+            // `unknown` already includes `null`.
+            if column.nullable && type_name != "unknown" {
+                type_name.push_str(" | null");
+            }
+            properties.push_str(&format!(
+                "  {}: {type_name};\n",
+                property_name(&column.name)
+            ));
+        }
+
+        let doc = match &model.table.comment {
+            Some(comment) => CommentStyle::BLOCK.render(comment.lines(), ""),
+            None => String::new(),
+        };
+
+        Ok(match self.declaration {
+            Declaration::Interface => format!(
+                "{doc}export interface {} {{\n{properties}}}\n",
+                model.class_name
+            ),
+            Declaration::Type => format!(
+                "{doc}export type {} = {{\n{properties}}};\n",
+                model.class_name
+            ),
+        })
+    }
+}
+
+/// Quotes column names that aren't valid identifiers, e.g. `first-name`.
+fn property_name(name: &str) -> String {
+    let mut chars = name.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+
+    match valid {
+        true => name.to_string(),
+        false => ts_string(name),
+    }
+}
+
+/// Quotes `s` as a single-quoted TypeScript string literal.
+fn ts_string(s: &str) -> String {
+    string_literal(s, '\'')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lang::testing::render;
+
+    #[test]
+    fn property_names() {
+        assert_eq!(property_name("user_id"), "user_id");
+        assert_eq!(property_name("$ref"), "$ref");
+        assert_eq!(property_name("first-name"), "'first-name'");
+        assert_eq!(property_name("2fa"), "'2fa'");
+    }
+
+    #[test]
+    fn unions() {
+        let typescript = TypeScript::new(&Config::default()).unwrap();
+        let status = SqlType::Enum {
+            values: vec![String::from("on"), String::from("off")],
+        };
+        assert_eq!(typescript.type_of(&status), "'on' | 'off'");
+        assert_eq!(
+            typescript.type_of(&SqlType::Array {
+                element: Box::new(status)
+            }),
+            "('on' | 'off')[]"
+        );
+
+        let code = render(
+            "lang = \"typescript\"",
+            "CREATE TABLE t (meta JSON, status ENUM('on', 'off'))",
+        );
+        assert!(code.contains("  meta: unknown;\n"));
+        assert!(code.contains("  status: 'on' | 'off' | null;\n"));
+    }
+
+    #[test]
+    fn decimals_are_strings() {
+        let code = render(
+            "lang = \"typescript\"",
+            "CREATE TABLE t (a DECIMAL NOT NULL, b NUMERIC(10, 2) NOT NULL)",
+        );
+        assert!(code.contains("  a: string;\n"));
+        assert!(code.contains("  b: string;\n"));
+    }
+}
@@ -1,14 +1,19 @@
-use anyhow::anyhow;
+use anyhow::{Context, anyhow};
 use clap::Parser;
-use cli::Command;
 use cli::config;
-use cli::config::LangOption;
+use cli::{Command, MigrateCommand, SchemaCommand};
 use colored::Colorize;
-use lang::php;
+use lang::dump::{Dump, DumpFormat};
+use lang::php::migration::{Framework, Migration};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use sql::dialect::Dialect;
+use sql::ir;
+use sql::migrate::{Change, Snapshot};
+use sql::query::NamedQuery;
 use sql::schema::Formatter;
 use std::io::{Read, Write};
 use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
 use std::{fs, io};
 
 mod cli;
@@ -67,66 +72,134 @@ fn main() -> anyhow::Result<()> {
                 }
             });
         }
-        None => {
-            if !config_file.exists() {
-                return Err(anyhow!(
-                    "no config file found at specified path: {:?}. Run {}.",
-                    config_file,
-                    "quaff init".yellow(),
-                ));
-            }
+        Some(Command::Migrate { command }) => {
+            let config = read_config(&config_file)?;
+            let dialect = Dialect::from_str(&config.dialect)?;
+            let current = Snapshot::from_dir(cwd.join("sql"))?;
+
+            match command {
+                MigrateCommand::Diff { from, snapshot } => {
+                    let previous = match (from, snapshot) {
+                        (_, Some(snapshot)) => Snapshot::load(snapshot)?,
+                        (Some(git_ref), None) => Snapshot::from_git(git_ref, "sql")?,
+                        (None, None) => return Err(anyhow!("pass --from or --snapshot")),
+                    };
 
-            let config_data = fs::read_to_string(&config_file)?;
-            let config: config::Config = toml::from_str(&config_data)?;
-
-            let dir_entries = fs::read_dir(cwd.join("sql"))?;
-            let models_dir = cwd.join("models");
-            fs::create_dir_all(&models_dir)?;
-
-            for entry in dir_entries {
-                let entry = entry?;
-                if let Some(ext) = entry.path().extension() {
-                    if ext != "sql" {
-                        return Err(anyhow!(
-                            "file {} does not have a valid extension",
-                            entry.path().display()
-                        ));
+                    let formatter = Formatter {
+                        minify: false,
+                        dialect,
+                    };
+                    let changes = sql::migrate::diff(&previous.tables, &current.tables, dialect);
+                    if changes.is_empty() {
+                        eprintln!("No schema changes.");
+                    }
+                    for change in changes {
+                        match change {
+                            // sqlparser doesn't know every dialect's ALTER
+                            // syntax, so what it can't parse is printed as is.
+                            Change::Sql(sql) => match formatter.run(&sql) {
+                                Ok(formatted) => println!("{formatted}\n"),
+                                Err(_) => println!("{sql};\n"),
+                            },
+                            Change::Note(note) => println!("-- TODO: {note}\n"),
+                        }
+                    }
+                }
+                MigrateCommand::Snapshot { output } => {
+                    current.save(output)?;
+                    println!("{}: {}", "Wrote".green(), output.display());
+                }
+                MigrateCommand::Export { framework, output } => {
+                    let framework = Framework::from_str(framework)?;
+                    let output = cwd.join(match output {
+                        Some(output) => output.clone(),
+                        None => framework.default_dir().into(),
+                    });
+                    let migrations = sql::load_tables(cwd.join("sql"))?
+                        .into_iter()
+                        .map(|(_, table)| Migration::new(table, framework, dialect))
+                        .collect();
+                    let migrations = ir::dependency_order(migrations);
+
+                    // One second apart, so the files sort in the order they
+                    // have to run.
+                    let start = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+                    fs::create_dir_all(&output)?;
+                    for (i, migration) in migrations.iter().enumerate() {
+                        let path = output.join(migration.file_name(start + i as u64));
+                        fs::write(&path, migration.to_string())?;
+                        println!("{}: {}", "Wrote".green(), path.display());
                     }
-                } else {
-                    return Err(anyhow!(
-                        "file {} does not have a valid extension",
-                        entry.path().display()
-                    ));
                 }
-
-                let stmts = sql::parse_file(entry.path())?;
-
-                let class = match config::LangOption::from_str(&config.lang) {
-                    Ok(LangOption::PHP) => php::Class::new(stmts)?,
-                    Err(e) => return Err(e),
-                };
-
-                let file_php = format!(
-                    "{}.php",
-                    entry
-                        .file_name()
-                        .into_string()
-                        .unwrap()
-                        .strip_suffix(".sql")
-                        .unwrap_or("")
-                );
-                fs::write(
-                    models_dir.join(&file_php),
-                    format!("<?php\nnamespace Quaff;\nuse DateTime;\n\n{class}"),
-                )?;
-                println!(
-                    "{}: {}",
-                    "Wrote".green(),
-                    models_dir.join(&file_php).display()
-                );
             }
         }
+        Some(Command::Schema { command }) => match command {
+            SchemaCommand::Dump { format } => {
+                let config = read_config(&config_file)?;
+                let format = DumpFormat::from_str(format)?;
+                let backend = lang::backend(&config)?;
+                let models = lang::load_models(&cwd.join("sql"), &config)?;
+
+                println!("{}", Dump::new(&models, backend.as_ref()).render(format)?);
+            }
+        },
+        None => {
+            let config = read_config(&config_file)?;
+            let queries = sql::query::load_queries(&cwd.join("queries"))?;
+
+            config
+                .targets()?
+                .par_iter()
+                .map(|(config, output)| {
+                    generate(&cwd, config, &cwd.join(output), &queries)
+                        .with_context(|| format!("generating {}", config.lang))
+                })
+                .collect::<anyhow::Result<()>>()?;
+        }
     }
 
     Ok(())
 }
+
+/// Writes the files `config.lang` generates into `output`.
+fn generate(
+    cwd: &std::path::Path,
+    config: &config::Config,
+    output: &std::path::Path,
+    queries: &[(String, Vec<NamedQuery>)],
+) -> anyhow::Result<()> {
+    let backend = lang::backend(config)?;
+    let models = lang::load_models(&cwd.join("sql"), config)?;
+    if !queries.is_empty() && !backend.renders_queries() {
+        eprintln!(
+            "{}: {} doesn't generate code for queries/; skipping them",
+            "Warning".yellow(),
+            config.lang
+        );
+    }
+
+    fs::create_dir_all(output)?;
+    for file in lang::generate(backend.as_ref(), &models, queries)? {
+        let path = output.join(&file.path);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, file.contents)?;
+        println!("{}: {}", "Wrote".green(), path.display());
+    }
+
+    Ok(())
+}
+
+fn read_config(config_file: &std::path::Path) -> anyhow::Result<config::Config> {
+    if !config_file.exists() {
+        return Err(anyhow!(
+            "no config file found at specified path: {:?}. Run {}.",
+            config_file,
+            "quaff init".yellow(),
+        ));
+    }
+
+    let config_data = fs::read_to_string(config_file)?;
+    Ok(toml::from_str(&config_data)?)
+}
@@ -0,0 +1,63 @@
+use anyhow::anyhow;
+use sqlparser::dialect::{
+    GenericDialect, MsSqlDialect, MySqlDialect, PostgreSqlDialect, SQLiteDialect,
+};
+use std::str::FromStr;
+
+/// The database the generated code talks to.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Dialect {
+    #[default]
+    Generic,
+    MySql,
+    Postgres,
+    Sqlite,
+    MsSql,
+}
+
+impl Dialect {
+    /// Quotes `ident` as an identifier, escaping any embedded quote characters.
+    pub fn quote_ident(&self, ident: &str) -> String {
+        match self {
+            Dialect::MySql => format!("`{}`", ident.replace('`', "``")),
+            Dialect::MsSql => format!("[{}]", ident.replace(']', "]]")),
+            Dialect::Generic | Dialect::Postgres | Dialect::Sqlite => {
+                format!("\"{}\"", ident.replace('"', "\"\""))
+            }
+        }
+    }
+
+    /// Quotes each part of a qualified name and joins them with `.`.
+    pub fn quote_qualified(&self, parts: &[String]) -> String {
+        parts
+            .iter()
+            .map(|part| self.quote_ident(part))
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    /// The `sqlparser` dialect for SQL written against this database.
+    pub fn parser(&self) -> Box<dyn sqlparser::dialect::Dialect> {
+        match self {
+            Dialect::Generic => Box::new(GenericDialect {}),
+            Dialect::MySql => Box::new(MySqlDialect {}),
+            Dialect::Postgres => Box::new(PostgreSqlDialect {}),
+            Dialect::Sqlite => Box::new(SQLiteDialect {}),
+            Dialect::MsSql => Box::new(MsSqlDialect {}),
+        }
+    }
+}
+
+impl FromStr for Dialect {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "generic" => Ok(Self::Generic),
+            "mysql" | "mariadb" => Ok(Self::MySql),
+            "postgres" | "postgresql" => Ok(Self::Postgres),
+            "sqlite" => Ok(Self::Sqlite),
+            "mssql" | "sqlserver" => Ok(Self::MsSql),
+            _ => Err(anyhow!("unknown SQL dialect: {s}")),
+        }
+    }
+}
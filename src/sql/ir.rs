@@ -0,0 +1,938 @@
+//! A dialect- and language-neutral view of the tables declared in `sql/`,
+//! extracted once from the parsed statements and shared by every generator.
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::{
+    ArrayElemTypeDef, BinaryLength, CharLengthUnits, CharacterLength, ColumnOption, CommentDef,
+    CommentObject, CreateIndex, DataType, EnumMember, ExactNumberInfo, Expr, Ident, ObjectName,
+    Statement, TableConstraint, TimezoneInfo,
+};
+use sqlparser::dialect::GenericDialect;
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::{Parser, ParserError};
+use sqlparser::tokenizer::{Token, Word};
+use thiserror::Error;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Table {
+    pub name: String,
+    /// Leading parts of a qualified table name, e.g. `billing` in `billing.invoices`.
+    pub schema: Vec<String>,
+    pub comment: Option<String>,
+    pub columns: Vec<Column>,
+    /// Column-level constraints in column order, then table-level ones.
+    pub constraints: Vec<Constraint>,
+    /// Non-constraint indexes, including those from `CREATE INDEX`.
+    pub indexes: Vec<Index>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(into = "ColumnRecord", try_from = "ColumnRecord")]
+pub struct Column {
+    pub name: String,
+    /// The type as written in the schema.
+    pub data_type: DataType,
+    pub sql_type: SqlType,
+    /// Whether the column accepts NULL: it isn't `NOT NULL` or part of the
+    /// primary key.
+    pub nullable: bool,
+    pub default: Option<Expr>,
+    /// Assigned by the database: identity, auto-increment, serial and
+    /// computed columns.
+    pub generated: bool,
+    pub comment: Option<String>,
+    /// The options not modeled above, as written, e.g. `AUTO_INCREMENT` or
+    /// `ON UPDATE CURRENT_TIMESTAMP`.
+    pub options: Vec<String>,
+}
+
+/// A column type, normalized across dialects.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum SqlType {
+    /// An integer stored in `bytes` bytes.
+    Integer {
+        bytes: u8,
+        unsigned: bool,
+    },
+    /// A binary floating point number, single or double precision.
+    Float {
+        double: bool,
+    },
+    /// An exact numeric.
+    Decimal {
+        precision: Option<u64>,
+        scale: Option<u64>,
+    },
+    Boolean,
+    /// A fixed-length character string.
+    Char {
+        length: Option<MaxLength>,
+    },
+    /// A bounded variable-length character string.
+    VarChar {
+        length: Option<MaxLength>,
+    },
+    /// An unbounded character string.
+    Text,
+    Binary {
+        length: Option<u64>,
+    },
+    Date,
+    Time,
+    Timestamp {
+        time_zone: bool,
+    },
+    Interval,
+    Json,
+    Uuid,
+    Array {
+        element: Box<SqlType>,
+    },
+    Enum {
+        values: Vec<String>,
+    },
+    /// Anything else, as written in the schema.
+    Other {
+        name: String,
+    },
+}
+
+/// The declared maximum length of a character or binary column.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct MaxLength {
+    pub value: u64,
+    /// Whether the length counts bytes rather than characters.
+    pub bytes: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Constraint {
+    PrimaryKey {
+        name: Option<String>,
+        columns: Vec<String>,
+    },
+    Unique {
+        name: Option<String>,
+        columns: Vec<String>,
+    },
+    ForeignKey(ForeignKey),
+    Check {
+        name: Option<String>,
+        #[serde(with = "sql_expr")]
+        expr: Box<Expr>,
+    },
+}
+
+/// A foreign key from some of the table's columns to another table.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForeignKey {
+    pub name: Option<String>,
+    pub columns: Vec<String>,
+    /// The referenced table's name, schema parts first.
+    pub foreign_table: Vec<String>,
+    pub referred_columns: Vec<String>,
+    pub on_delete: Option<String>,
+    pub on_update: Option<String>,
+}
+
+/// An index over some of the table's columns.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Index {
+    pub name: Option<String>,
+    pub columns: Vec<String>,
+    pub unique: bool,
+}
+
+/// A column as saved in a snapshot: the type and default as SQL, without
+/// anything derived from them.
+#[derive(Serialize, Deserialize)]
+struct ColumnRecord {
+    name: String,
+    #[serde(rename = "type")]
+    data_type: String,
+    nullable: bool,
+    default: Option<String>,
+    generated: bool,
+    comment: Option<String>,
+    options: Vec<String>,
+}
+
+impl From<Column> for ColumnRecord {
+    fn from(column: Column) -> Self {
+        Self {
+            name: column.name,
+            data_type: column.data_type.to_string(),
+            nullable: column.nullable,
+            default: column.default.map(|default| default.to_string()),
+            generated: column.generated,
+            comment: column.comment,
+            options: column.options,
+        }
+    }
+}
+
+impl TryFrom<ColumnRecord> for Column {
+    type Error = ParserError;
+
+    fn try_from(record: ColumnRecord) -> Result<Self, Self::Error> {
+        let data_type = parser(&record.data_type)?.parse_data_type()?;
+        Ok(Self {
+            name: record.name,
+            sql_type: SqlType::from(&data_type),
+            data_type,
+            nullable: record.nullable,
+            default: record
+                .default
+                .map(|default| parser(&default)?.parse_expr())
+                .transpose()?,
+            generated: record.generated,
+            comment: record.comment,
+            options: record.options,
+        })
+    }
+}
+
+/// Saves an expression as the SQL it prints as.
+mod sql_expr {
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+    use sqlparser::ast::Expr;
+
+    pub fn serialize<S: Serializer>(expr: &Expr, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(expr)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Box<Expr>, D::Error> {
+        let sql = String::deserialize(deserializer)?;
+        super::parser(&sql)
+            .and_then(|mut parser| parser.parse_expr())
+            .map(Box::new)
+            .map_err(D::Error::custom)
+    }
+}
+
+/// A parser for a fragment of a saved schema, which is always written in the
+/// generic dialect.
+fn parser(sql: &str) -> Result<Parser<'static>, ParserError> {
+    Parser::new(&GenericDialect {}).try_with_sql(sql)
+}
+
+#[derive(Debug, Error)]
+pub enum TableError {
+    #[error("Table name is empty: {0}")]
+    EmptyTableName(String),
+    #[error("no CREATE TABLE statement; each schema file declares one table")]
+    NoCreateTable,
+    #[error("{0} CREATE TABLE statements; each schema file declares one table")]
+    SeveralCreateTables(usize),
+}
+
+impl Table {
+    /// Builds the table declared by the statements of one schema file: a
+    /// `CREATE TABLE` plus any `COMMENT ON` and `CREATE INDEX` for it.
+    pub fn new(mut stmts: Vec<Statement>) -> anyhow::Result<Self> {
+        match stmts
+            .iter()
+            .filter(|stmt| matches!(stmt, Statement::CreateTable(_)))
+            .count()
+        {
+            0 => return Err(TableError::NoCreateTable.into()),
+            1 => {}
+            n => return Err(TableError::SeveralCreateTables(n).into()),
+        }
+
+        // `COMMENT ON` and `CREATE INDEX` may come before the table they
+        // apply to.
+        stmts.sort_by_key(|stmt| !matches!(stmt, Statement::CreateTable(_)));
+
+        let mut table = Self {
+            name: String::new(),
+            schema: vec![],
+            comment: None,
+            columns: vec![],
+            constraints: vec![],
+            indexes: vec![],
+        };
+
+        for stmt in stmts {
+            match stmt {
+                Statement::Comment {
+                    object_type,
+                    object_name,
+                    comment,
+                    ..
+                } => table.apply_comment(object_type, &object_name, comment),
+                Statement::CreateIndex(index) => table.apply_index(index),
+                Statement::CreateTable(create) => {
+                    let (schema, name) = split_table_name(&create.name)?;
+                    table.name = name;
+                    table.schema = schema;
+                    table.comment = create.comment.and_then(|comment| match comment {
+                        CommentDef::WithEq(s)
+                        | CommentDef::WithoutEq(s)
+                        | CommentDef::AfterColumnDefsWithoutEq(s) => non_blank(s),
+                    });
+
+                    let mut columns = vec![];
+                    let mut constraints = vec![];
+                    for column in create.columns {
+                        let mut not_null = false;
+                        let mut col = Column {
+                            name: column.name.value.clone(),
+                            sql_type: SqlType::from(&column.data_type),
+                            generated: is_serial_type(&column.data_type),
+                            data_type: column.data_type,
+                            // SQL columns are nullable unless constrained otherwise.
+                            nullable: true,
+                            default: None,
+                            comment: None,
+                            options: vec![],
+                        };
+
+                        for option in column.options {
+                            let name = option.name.map(|name| name.value);
+                            match option.option {
+                                ColumnOption::Unique { is_primary, .. } => {
+                                    let columns = vec![col.name.clone()];
+                                    constraints.push(match is_primary {
+                                        true => {
+                                            not_null = true;
+                                            Constraint::PrimaryKey { name, columns }
+                                        }
+                                        false => Constraint::Unique { name, columns },
+                                    });
+                                }
+                                ColumnOption::ForeignKey {
+                                    foreign_table,
+                                    referred_columns,
+                                    on_delete,
+                                    on_update,
+                                    ..
+                                } => constraints.push(Constraint::ForeignKey(ForeignKey {
+                                    name,
+                                    columns: vec![col.name.clone()],
+                                    foreign_table: name_parts(&foreign_table),
+                                    referred_columns: idents(&referred_columns),
+                                    on_delete: on_delete.map(|action| action.to_string()),
+                                    on_update: on_update.map(|action| action.to_string()),
+                                })),
+                                ColumnOption::Null => not_null = false,
+                                ColumnOption::NotNull => not_null = true,
+                                ColumnOption::Default(expr) => col.default = Some(expr),
+                                option @ (ColumnOption::Generated { .. }
+                                | ColumnOption::Identity(_)) => {
+                                    col.generated = true;
+                                    col.options.push(option.to_string());
+                                }
+                                ColumnOption::DialectSpecific(tokens)
+                                    if is_auto_increment(&tokens) =>
+                                {
+                                    col.generated = true;
+                                    col.options
+                                        .push(ColumnOption::DialectSpecific(tokens).to_string());
+                                }
+                                ColumnOption::Comment(comment) => col.comment = non_blank(comment),
+                                ColumnOption::Check(expr) => constraints.push(Constraint::Check {
+                                    name,
+                                    expr: Box::new(expr),
+                                }),
+                                option => col.options.push(option.to_string()),
+                            }
+                        }
+
+                        col.nullable = !not_null;
+                        columns.push(col);
+                    }
+
+                    for constraint in create.constraints {
+                        match constraint {
+                            TableConstraint::PrimaryKey { name, columns, .. } => {
+                                constraints.push(Constraint::PrimaryKey {
+                                    name: name.map(|name| name.value),
+                                    columns: idents(&columns),
+                                })
+                            }
+                            TableConstraint::Unique { name, columns, .. } => {
+                                constraints.push(Constraint::Unique {
+                                    name: name.map(|name| name.value),
+                                    columns: idents(&columns),
+                                })
+                            }
+                            TableConstraint::ForeignKey {
+                                name,
+                                columns,
+                                foreign_table,
+                                referred_columns,
+                                on_delete,
+                                on_update,
+                                ..
+                            } => constraints.push(Constraint::ForeignKey(ForeignKey {
+                                name: name.map(|name| name.value),
+                                columns: idents(&columns),
+                                foreign_table: name_parts(&foreign_table),
+                                referred_columns: idents(&referred_columns),
+                                on_delete: on_delete.map(|action| action.to_string()),
+                                on_update: on_update.map(|action| action.to_string()),
+                            })),
+                            TableConstraint::Check { name, expr } => {
+                                constraints.push(Constraint::Check {
+                                    name: name.map(|name| name.value),
+                                    expr,
+                                })
+                            }
+                            TableConstraint::Index { name, columns, .. } => {
+                                table.indexes.push(Index {
+                                    name: name.map(|name| name.value),
+                                    columns: idents(&columns),
+                                    unique: false,
+                                })
+                            }
+                            TableConstraint::FulltextOrSpatial { .. } => {}
+                        }
+                    }
+
+                    // Primary key columns never accept NULL.
+                    for key in constraints.iter().flat_map(|constraint| match constraint {
+                        Constraint::PrimaryKey { columns, .. } => columns.as_slice(),
+                        _ => &[],
+                    }) {
+                        if let Some(column) = columns.iter_mut().find(|column| &column.name == key)
+                        {
+                            column.nullable = false;
+                        }
+                    }
+
+                    table.columns = columns;
+                    table.constraints = constraints;
+                }
+                _ => return Err(anyhow!("SQL must be a create table statment")),
+            }
+        }
+
+        Ok(table)
+    }
+
+    /// Applies a Postgres-style `COMMENT ON TABLE`/`COMMENT ON COLUMN` to the
+    /// table or one of its columns. Comments on other tables are ignored.
+    fn apply_comment(
+        &mut self,
+        object_type: CommentObject,
+        object_name: &ObjectName,
+        comment: Option<String>,
+    ) {
+        let Ok((mut path, name)) = split_table_name(object_name) else {
+            return;
+        };
+        let comment = comment.and_then(non_blank);
+
+        match object_type {
+            CommentObject::Table if name == self.name => self.comment = comment,
+            CommentObject::Column if path.pop().as_ref() == Some(&self.name) => {
+                if let Some(column) = self.columns.iter_mut().find(|column| column.name == name) {
+                    column.comment = comment;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Records a `CREATE INDEX` on this table. Indexes on other tables are
+    /// ignored.
+    fn apply_index(&mut self, index: CreateIndex) {
+        let Ok((_, table)) = split_table_name(&index.table_name) else {
+            return;
+        };
+        if table != self.name {
+            return;
+        }
+
+        self.indexes.push(Index {
+            name: index.name.map(|name| name_parts(&name).join(".")),
+            columns: index
+                .columns
+                .iter()
+                .map(|column| match &column.column.expr {
+                    Expr::Identifier(ident) => ident.value.clone(),
+                    expr => expr.to_string(),
+                })
+                .collect(),
+            unique: index.unique,
+        });
+    }
+
+    /// Primary key columns, in key order.
+    pub fn primary_key(&self) -> Vec<&str> {
+        self.constraints
+            .iter()
+            .flat_map(|constraint| match constraint {
+                Constraint::PrimaryKey { columns, .. } => columns.as_slice(),
+                _ => &[],
+            })
+            .map(String::as_str)
+            .collect()
+    }
+
+    pub fn foreign_keys(&self) -> impl Iterator<Item = &ForeignKey> {
+        self.constraints
+            .iter()
+            .filter_map(|constraint| match constraint {
+                Constraint::ForeignKey(key) => Some(key),
+                _ => None,
+            })
+    }
+
+    /// `CHECK` constraints with their names, column-level ones first.
+    pub fn checks(&self) -> impl Iterator<Item = (Option<&str>, &Expr)> {
+        self.constraints
+            .iter()
+            .filter_map(|constraint| match constraint {
+                Constraint::Check { name, expr } => Some((name.as_deref(), expr.as_ref())),
+                _ => None,
+            })
+    }
+
+    /// Whether `column` alone is covered by a unique constraint.
+    pub fn is_unique(&self, column: &str) -> bool {
+        self.constraints.iter().any(|constraint| match constraint {
+            Constraint::Unique { columns, .. } => columns.len() == 1 && columns[0] == column,
+            _ => false,
+        })
+    }
+
+    /// The table's name, schema parts first.
+    pub fn qualified_name(&self) -> Vec<String> {
+        let mut parts = self.schema.clone();
+        parts.push(self.name.clone());
+        parts
+    }
+
+    /// Whether `name`, schema parts first, refers to this table.
+    pub fn is_named(&self, name: &[String]) -> bool {
+        name.split_last()
+            .is_some_and(|(table, schema)| *table == self.name && schema == self.schema)
+    }
+}
+
+impl AsRef<Table> for Table {
+    fn as_ref(&self) -> &Table {
+        self
+    }
+}
+
+/// Orders `tables` so that every table comes after the tables it references.
+/// Tables in a reference cycle keep their declaration order.
+pub fn dependency_order<T: AsRef<Table>>(tables: Vec<T>) -> Vec<T> {
+    let mut ordered = vec![];
+    let mut remaining = tables;
+
+    while !remaining.is_empty() {
+        let ready = remaining.iter().position(|table| {
+            let table = table.as_ref();
+            table.foreign_keys().all(|key| {
+                table.is_named(&key.foreign_table)
+                    || !remaining
+                        .iter()
+                        .any(|other| other.as_ref().is_named(&key.foreign_table))
+            })
+        });
+        ordered.push(remaining.remove(ready.unwrap_or(0)));
+    }
+
+    ordered
+}
+
+impl SqlType {
+    pub fn max_length(&self) -> Option<MaxLength> {
+        match self {
+            SqlType::Char { length } | SqlType::VarChar { length } => *length,
+            SqlType::Binary { length } => length.map(|value| MaxLength { value, bytes: true }),
+            _ => None,
+        }
+    }
+
+    pub fn is_unsigned(&self) -> bool {
+        matches!(self, SqlType::Integer { unsigned: true, .. })
+    }
+}
+
+impl From<&DataType> for SqlType {
+    fn from(value: &DataType) -> Self {
+        let int = |bytes: u8, unsigned: bool| SqlType::Integer { bytes, unsigned };
+        let decimal = |info: &ExactNumberInfo| match info {
+            ExactNumberInfo::None => SqlType::Decimal {
+                precision: None,
+                scale: None,
+            },
+            ExactNumberInfo::Precision(precision) => SqlType::Decimal {
+                precision: Some(*precision),
+                scale: None,
+            },
+            ExactNumberInfo::PrecisionAndScale(precision, scale) => SqlType::Decimal {
+                precision: Some(*precision),
+                scale: Some(*scale),
+            },
+        };
+
+        match value {
+            DataType::TinyInt(_) => int(1, false),
+            DataType::TinyIntUnsigned(_) | DataType::UTinyInt | DataType::UInt8 => int(1, true),
+            DataType::Int2(_) | DataType::SmallInt(_) | DataType::Int16 => int(2, false),
+            DataType::Int2Unsigned(_)
+            | DataType::SmallIntUnsigned(_)
+            | DataType::USmallInt
+            | DataType::UInt16 => int(2, true),
+            DataType::MediumInt(_)
+            | DataType::Int(_)
+            | DataType::Int4(_)
+            | DataType::Int32
+            | DataType::Integer(_)
+            | DataType::Signed
+            | DataType::SignedInteger => int(4, false),
+            DataType::MediumIntUnsigned(_)
+            | DataType::IntUnsigned(_)
+            | DataType::Int4Unsigned(_)
+            | DataType::IntegerUnsigned(_)
+            | DataType::UInt32
+            | DataType::Unsigned
+            | DataType::UnsignedInteger => int(4, true),
+            DataType::BigInt(_) | DataType::Int8(_) | DataType::Int64 => int(8, false),
+            DataType::BigIntUnsigned(_)
+            | DataType::Int8Unsigned(_)
+            | DataType::UBigInt
+            | DataType::UInt64 => int(8, true),
+            DataType::Int128 | DataType::HugeInt => int(16, false),
+            DataType::UInt128 | DataType::UHugeInt => int(16, true),
+            DataType::Int256 => int(32, false),
+            DataType::UInt256 => int(32, true),
+            DataType::Custom(name, _) if is_serial(name) => {
+                let name = name.to_string().to_lowercase();
+                match name.as_str() {
+                    "smallserial" | "serial2" => int(2, false),
+                    "bigserial" | "serial8" => int(8, false),
+                    _ => int(4, false),
+                }
+            }
+
+            DataType::Real | DataType::Float4 | DataType::Float32 => {
+                SqlType::Float { double: false }
+            }
+            DataType::Float(Some(precision)) if *precision <= 24 => {
+                SqlType::Float { double: false }
+            }
+            DataType::Float(_)
+            | DataType::Float8
+            | DataType::Float64
+            | DataType::Double(_)
+            | DataType::DoublePrecision => SqlType::Float { double: true },
+            DataType::Numeric(info)
+            | DataType::Decimal(info)
+            | DataType::BigNumeric(info)
+            | DataType::BigDecimal(info)
+            | DataType::Dec(info) => decimal(info),
+
+            DataType::Bool | DataType::Boolean => SqlType::Boolean,
+
+            DataType::Character(length) | DataType::Char(length) => SqlType::Char {
+                length: length.as_ref().and_then(max_length),
+            },
+            DataType::FixedString(length) => SqlType::Char {
+                length: Some(MaxLength {
+                    value: *length,
+                    bytes: true,
+                }),
+            },
+            DataType::CharacterVarying(length)
+            | DataType::CharVarying(length)
+            | DataType::Varchar(length)
+            | DataType::Nvarchar(length) => SqlType::VarChar {
+                length: length.as_ref().and_then(max_length),
+            },
+            DataType::Text
+            | DataType::TinyText
+            | DataType::MediumText
+            | DataType::LongText
+            | DataType::String(_)
+            | DataType::Clob(_)
+            | DataType::CharacterLargeObject(_)
+            | DataType::CharLargeObject(_) => SqlType::Text,
+
+            DataType::Binary(length) => SqlType::Binary { length: *length },
+            DataType::Varbinary(length) => SqlType::Binary {
+                length: match length {
+                    Some(BinaryLength::IntegerLength { length }) => Some(*length),
+                    _ => None,
+                },
+            },
+            DataType::Blob(_)
+            | DataType::TinyBlob
+            | DataType::MediumBlob
+            | DataType::LongBlob
+            | DataType::Bytes(_)
+            | DataType::Bytea => SqlType::Binary { length: None },
+
+            DataType::Date | DataType::Date32 => SqlType::Date,
+            DataType::Time(..) => SqlType::Time,
+            DataType::Datetime(_) | DataType::Datetime64(..) | DataType::TimestampNtz => {
+                SqlType::Timestamp { time_zone: false }
+            }
+            DataType::Timestamp(_, time_zone) => SqlType::Timestamp {
+                time_zone: matches!(time_zone, TimezoneInfo::WithTimeZone | TimezoneInfo::Tz),
+            },
+            DataType::Interval => SqlType::Interval,
+
+            DataType::JSON | DataType::JSONB => SqlType::Json,
+            DataType::Uuid => SqlType::Uuid,
+            DataType::Array(
+                ArrayElemTypeDef::AngleBracket(element)
+                | ArrayElemTypeDef::SquareBracket(element, _)
+                | ArrayElemTypeDef::Parenthesis(element),
+            ) => SqlType::Array {
+                element: Box::new(SqlType::from(element.as_ref())),
+            },
+            DataType::Enum(members, _) => SqlType::Enum {
+                values: members
+                    .iter()
+                    .map(|member| match member {
+                        EnumMember::Name(name) | EnumMember::NamedValue(name, _) => name.clone(),
+                    })
+                    .collect(),
+            },
+
+            other => SqlType::Other {
+                name: other.to_string(),
+            },
+        }
+    }
+}
+
+fn max_length(length: &CharacterLength) -> Option<MaxLength> {
+    match length {
+        CharacterLength::IntegerLength { length, unit } => Some(MaxLength {
+            value: *length,
+            bytes: matches!(unit, Some(CharLengthUnits::Octets)),
+        }),
+        CharacterLength::Max => None,
+    }
+}
+
+/// Matches MySQL's `AUTO_INCREMENT` and SQLite's `AUTOINCREMENT`, which
+/// sqlparser keeps as raw tokens.
+fn is_auto_increment(tokens: &[Token]) -> bool {
+    tokens.iter().any(|token| {
+        matches!(
+            token,
+            Token::Word(Word {
+                keyword: Keyword::AUTO_INCREMENT | Keyword::AUTOINCREMENT,
+                ..
+            })
+        )
+    })
+}
+
+/// `comment`, unless it has nothing to render.
+fn non_blank(comment: String) -> Option<String> {
+    match comment.trim().is_empty() {
+        true => None,
+        false => Some(comment),
+    }
+}
+
+/// Matches Postgres' `SERIAL` pseudo-types, which imply a sequence default.
+pub fn is_serial(name: &ObjectName) -> bool {
+    let name = name.to_string().to_lowercase();
+    matches!(
+        name.as_str(),
+        "serial" | "serial2" | "serial4" | "serial8" | "smallserial" | "bigserial"
+    )
+}
+
+fn is_serial_type(data_type: &DataType) -> bool {
+    match data_type {
+        DataType::Custom(name, _) => is_serial(name),
+        _ => false,
+    }
+}
+
+fn name_parts(name: &ObjectName) -> Vec<String> {
+    name.0
+        .iter()
+        .filter_map(|part| part.as_ident())
+        .map(|ident| ident.value.clone())
+        .collect()
+}
+
+fn idents(idents: &[Ident]) -> Vec<String> {
+    idents.iter().map(|ident| ident.value.clone()).collect()
+}
+
+/// Splits a possibly qualified table name into its schema parts and the table
+/// name itself, which is always the last part.
+pub fn split_table_name(name: &ObjectName) -> Result<(Vec<String>, String), TableError> {
+    let mut parts = name_parts(name);
+
+    if parts.iter().any(|part| part.is_empty()) {
+        return Err(TableError::EmptyTableName(name.to_string()));
+    }
+
+    match parts.pop() {
+        Some(table) => Ok((parts, table)),
+        None => Err(TableError::EmptyTableName(name.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lang::testing::table;
+
+    #[test]
+    fn comments_before_the_table() {
+        let table = table(
+            "COMMENT ON TABLE users IS 'Accounts';
+            COMMENT ON COLUMN users.email IS 'Login';
+            CREATE TABLE users (id INT, email TEXT);",
+        );
+
+        assert_eq!(table.comment.as_deref(), Some("Accounts"));
+        assert_eq!(table.columns[1].comment.as_deref(), Some("Login"));
+    }
+
+    #[test]
+    fn blank_comments_are_dropped() {
+        let table = table(
+            "CREATE TABLE users (id INT);
+            COMMENT ON TABLE users IS '  ';
+            COMMENT ON COLUMN users.id IS '';",
+        );
+
+        assert_eq!(table.comment, None);
+        assert_eq!(table.columns[0].comment, None);
+    }
+
+    #[test]
+    fn qualified_table_names() {
+        let name = |parts: &[&str]| {
+            ObjectName::from(
+                parts
+                    .iter()
+                    .map(|part| Ident::new(*part))
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        assert_eq!(
+            split_table_name(&name(&["users"])).unwrap(),
+            (vec![], "users".to_string())
+        );
+        assert_eq!(
+            split_table_name(&name(&["app", "billing", "invoices"])).unwrap(),
+            (
+                vec!["app".to_string(), "billing".to_string()],
+                "invoices".to_string()
+            )
+        );
+        assert!(split_table_name(&name(&["billing", ""])).is_err());
+        assert!(split_table_name(&name(&[])).is_err());
+
+        let table = table(r#"CREATE TABLE "billing"."invoices" (id INT);"#);
+        assert_eq!(table.schema, ["billing"]);
+        assert_eq!(table.name, "invoices");
+    }
+
+    #[test]
+    fn sql_types() {
+        let table = table(
+            "CREATE TABLE t (
+                a TINYINT, b INT UNSIGNED, c BIGSERIAL, d REAL, e DOUBLE PRECISION,
+                f DECIMAL(8, 2), g NUMERIC, h BOOLEAN, i CHAR(6), j VARCHAR(100),
+                k TEXT, l VARBINARY(16), m BYTEA, n DATE, o TIMESTAMP WITH TIME ZONE,
+                p DATETIME, q JSONB, r UUID, s INT[], t ENUM('a', 'b'), u POINT
+            );",
+        );
+        let types: Vec<_> = table
+            .columns
+            .iter()
+            .map(|column| &column.sql_type)
+            .collect();
+        let length = |value| {
+            Some(MaxLength {
+                value,
+                bytes: false,
+            })
+        };
+
+        assert_eq!(
+            types,
+            [
+                &SqlType::Integer {
+                    bytes: 1,
+                    unsigned: false
+                },
+                &SqlType::Integer {
+                    bytes: 4,
+                    unsigned: true
+                },
+                &SqlType::Integer {
+                    bytes: 8,
+                    unsigned: false
+                },
+                &SqlType::Float { double: false },
+                &SqlType::Float { double: true },
+                &SqlType::Decimal {
+                    precision: Some(8),
+                    scale: Some(2)
+                },
+                &SqlType::Decimal {
+                    precision: None,
+                    scale: None
+                },
+                &SqlType::Boolean,
+                &SqlType::Char { length: length(6) },
+                &SqlType::VarChar {
+                    length: length(100)
+                },
+                &SqlType::Text,
+                &SqlType::Binary { length: Some(16) },
+                &SqlType::Binary { length: None },
+                &SqlType::Date,
+                &SqlType::Timestamp { time_zone: true },
+                &SqlType::Timestamp { time_zone: false },
+                &SqlType::Json,
+                &SqlType::Uuid,
+                &SqlType::Array {
+                    element: Box::new(SqlType::Integer {
+                        bytes: 4,
+                        unsigned: false
+                    })
+                },
+                &SqlType::Enum {
+                    values: vec!["a".to_string(), "b".to_string()]
+                },
+                &SqlType::Other {
+                    name: "POINT".to_string()
+                },
+            ]
+        );
+        assert!(table.columns[2].generated);
+    }
+
+    #[test]
+    fn one_table_per_file() {
+        let error = |sql: &str| {
+            Table::new(crate::sql::parse_sql(sql).unwrap())
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert_eq!(
+            error("CREATE INDEX users_email ON users (email);"),
+            "no CREATE TABLE statement; each schema file declares one table"
+        );
+        assert_eq!(
+            error("CREATE TABLE a (id INT); CREATE TABLE b (id INT);"),
+            "2 CREATE TABLE statements; each schema file declares one table"
+        );
+    }
+}
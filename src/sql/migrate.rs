@@ -0,0 +1,729 @@
+use anyhow::{Context, anyhow};
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::Value;
+use std::{fs, path::Path, process::Command};
+
+use super::dialect::Dialect;
+use super::ir::{self, Column, Constraint, Index, Table};
+
+/// The schema declared in `sql/` at some point in time.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// The layout of the saved snapshot, [`Snapshot::VERSION`] for new ones.
+    pub version: u32,
+    /// The table declared by each file, in file name order.
+    pub tables: Vec<Table>,
+}
+
+impl Snapshot {
+    /// Bumped whenever the saved layout changes, so older snapshots are
+    /// rejected instead of misread.
+    pub const VERSION: u32 = 1;
+
+    fn new(tables: Vec<Table>) -> Self {
+        Self {
+            version: Self::VERSION,
+            tables,
+        }
+    }
+
+    /// Reads the schema files in `dir`, in file name order.
+    pub fn from_dir<P: AsRef<Path>>(dir: P) -> anyhow::Result<Self> {
+        let tables = super::load_tables(dir)?
+            .into_iter()
+            .map(|(_, table)| table)
+            .collect();
+
+        Ok(Self::new(tables))
+    }
+
+    /// Reads every `.sql` file in `dir` as of `git_ref`. `dir` is relative to
+    /// the current directory, which must be inside the repository.
+    pub fn from_git(git_ref: &str, dir: &str) -> anyhow::Result<Self> {
+        let listing = git(&["ls-tree", "--name-only", git_ref, "--", &format!("{dir}/")])?;
+        let mut files: Vec<&str> = listing
+            .lines()
+            .filter(|path| path.ends_with(".sql"))
+            .collect();
+        files.sort();
+
+        let tables = files
+            .into_iter()
+            .map(|file| {
+                let sql = git(&["show", &format!("{git_ref}:./{file}")])?;
+                Table::new(super::parse_sql(&sql)?)
+                    .with_context(|| format!("in {file} at {git_ref}"))
+            })
+            .collect::<anyhow::Result<_>>()?;
+
+        Ok(Self::new(tables))
+    }
+
+    pub fn load<P: AsRef<Path>>(file: P) -> anyhow::Result<Self> {
+        let file = file.as_ref();
+        let json: serde_json::Value = serde_json::from_str(&fs::read_to_string(file)?)?;
+        let version = json.get("version").and_then(|version| version.as_u64());
+        if version != Some(Self::VERSION.into()) {
+            return Err(anyhow!(
+                "{} isn't a version {} snapshot; save it again with `quaff migrate snapshot`",
+                file.display(),
+                Self::VERSION
+            ));
+        }
+
+        Ok(serde_json::from_value(json)?)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, file: P) -> anyhow::Result<()> {
+        fs::write(file, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+fn git(args: &[&str]) -> anyhow::Result<String> {
+    let output = Command::new("git").args(args).output()?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+/// One step of a migration.
+#[derive(Debug)]
+pub enum Change {
+    /// A statement to run.
+    Sql(String),
+    /// Something the dialect can't express, which has to be done by hand.
+    Note(String),
+}
+
+/// The statements that turn the schema in `old` into the one in `new`:
+/// created tables first, in foreign key order, then changes to existing
+/// tables, then dropped tables.
+pub fn diff(old: &[Table], new: &[Table], dialect: Dialect) -> Vec<Change> {
+    let find = |tables: &[Table], table: &Table| -> Option<usize> {
+        tables
+            .iter()
+            .position(|other| other.is_named(&table.qualified_name()))
+    };
+
+    let mut changes = vec![];
+
+    let created: Vec<&Table> = new
+        .iter()
+        .filter(|table| find(old, table).is_none())
+        .collect();
+    for table in ir::dependency_order(created) {
+        changes.extend(create_table(table, dialect));
+    }
+
+    for new_table in new {
+        if let Some(old_table) = find(old, new_table) {
+            changes.extend(Alter::new(&old[old_table], new_table, dialect).changes());
+        }
+    }
+
+    let dropped: Vec<&Table> = old
+        .iter()
+        .filter(|table| find(new, table).is_none())
+        .collect();
+    for table in ir::dependency_order(dropped).into_iter().rev() {
+        changes.push(Change::Sql(format!(
+            "DROP TABLE {}",
+            dialect.quote_qualified(&table.qualified_name())
+        )));
+    }
+
+    changes
+}
+
+/// `CREATE TABLE` for `table`, followed by its indexes and, in dialects that
+/// keep comments apart from the definition, its comments.
+pub fn create_table(table: &Table, dialect: Dialect) -> Vec<Change> {
+    let name = dialect.quote_qualified(&table.qualified_name());
+
+    // SQLite only accepts `AUTOINCREMENT` on an inline primary key.
+    let primary_key = table.primary_key();
+    let inline_key = match (dialect, primary_key.as_slice()) {
+        (Dialect::Sqlite, [column]) => Some(*column),
+        _ => None,
+    };
+
+    let columns = table
+        .columns
+        .iter()
+        .map(|column| definition(column, inline_key == Some(column.name.as_str()), dialect));
+    let constraints = table
+        .constraints
+        .iter()
+        .filter(|constraint| {
+            inline_key.is_none() || !matches!(constraint, Constraint::PrimaryKey { .. })
+        })
+        .map(|constraint| constraint_definition(constraint, dialect));
+
+    let mut sql = format!(
+        "CREATE TABLE {name} ({})",
+        columns.chain(constraints).collect::<Vec<_>>().join(", ")
+    );
+    if let (Dialect::MySql, Some(comment)) = (dialect, &table.comment) {
+        sql.push_str(&format!(" COMMENT = {}", string(comment)));
+    }
+
+    let mut changes = vec![Change::Sql(sql)];
+    for index in &table.indexes {
+        changes.push(create_index(table, index, dialect));
+    }
+    if separate_comments(dialect) {
+        if let Some(comment) = &table.comment {
+            changes.push(Change::Sql(format!(
+                "COMMENT ON TABLE {name} IS {}",
+                string(comment)
+            )));
+        }
+        for column in &table.columns {
+            if let Some(comment) = &column.comment {
+                changes.push(comment_on_column(&name, column, Some(comment), dialect));
+            }
+        }
+    }
+
+    changes
+}
+
+/// The definition of `column` in `CREATE TABLE` or `ADD COLUMN`.
+pub fn column_definition(column: &Column, dialect: Dialect) -> String {
+    definition(column, false, dialect)
+}
+
+fn definition(column: &Column, primary_key: bool, dialect: Dialect) -> String {
+    let mut definition = format!("{} {}", dialect.quote_ident(&column.name), column.data_type);
+    if !column.nullable {
+        definition.push_str(" NOT NULL");
+    }
+    if let Some(default) = &column.default {
+        definition.push_str(&format!(" DEFAULT {default}"));
+    }
+    if primary_key {
+        definition.push_str(" PRIMARY KEY");
+    }
+    for option in &column.options {
+        definition.push_str(&format!(" {option}"));
+    }
+    if let (Dialect::MySql, Some(comment)) = (dialect, &column.comment) {
+        definition.push_str(&format!(" COMMENT {}", string(comment)));
+    }
+
+    definition
+}
+
+/// The definition of `constraint` in `CREATE TABLE` or `ADD`.
+pub fn constraint_definition(constraint: &Constraint, dialect: Dialect) -> String {
+    let named = |name: &Option<String>| match name {
+        Some(name) => format!("CONSTRAINT {} ", dialect.quote_ident(name)),
+        None => String::new(),
+    };
+    let list = |columns: &[String]| {
+        columns
+            .iter()
+            .map(|column| dialect.quote_ident(column))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    match constraint {
+        Constraint::PrimaryKey { name, columns } => {
+            format!("{}PRIMARY KEY ({})", named(name), list(columns))
+        }
+        Constraint::Unique { name, columns } => {
+            format!("{}UNIQUE ({})", named(name), list(columns))
+        }
+        Constraint::ForeignKey(key) => {
+            let mut sql = format!(
+                "{}FOREIGN KEY ({}) REFERENCES {}",
+                named(&key.name),
+                list(&key.columns),
+                dialect.quote_qualified(&key.foreign_table)
+            );
+            if !key.referred_columns.is_empty() {
+                sql.push_str(&format!(" ({})", list(&key.referred_columns)));
+            }
+            if let Some(action) = &key.on_delete {
+                sql.push_str(&format!(" ON DELETE {action}"));
+            }
+            if let Some(action) = &key.on_update {
+                sql.push_str(&format!(" ON UPDATE {action}"));
+            }
+            sql
+        }
+        Constraint::Check { name, expr } => format!("{}CHECK ({expr})", named(name)),
+    }
+}
+
+/// Creates `index` on `table`.
+pub fn create_index(table: &Table, index: &Index, dialect: Dialect) -> Change {
+    let unique = if index.unique { "UNIQUE " } else { "" };
+    let name = match &index.name {
+        Some(name) => format!("{} ", dialect.quote_ident(name)),
+        None => String::new(),
+    };
+    let columns = index
+        .columns
+        .iter()
+        .map(|column| match column.contains('(') {
+            // An expression, kept as written.
+            true => column.clone(),
+            false => dialect.quote_ident(column),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let table = dialect.quote_qualified(&table.qualified_name());
+
+    Change::Sql(match dialect {
+        // MySQL indexes may be unnamed.
+        Dialect::MySql => format!("ALTER TABLE {table} ADD {unique}INDEX {name}({columns})"),
+        _ => format!("CREATE {unique}INDEX {name}ON {table} ({columns})"),
+    })
+}
+
+/// Adds `column` to `table`, and its comment in dialects that set it apart.
+pub fn add_column(table: &Table, column: &Column, dialect: Dialect) -> Vec<Change> {
+    let name = dialect.quote_qualified(&table.qualified_name());
+    let add = match dialect {
+        Dialect::MsSql => "ADD",
+        _ => "ADD COLUMN",
+    };
+    let mut changes = vec![Change::Sql(format!(
+        "ALTER TABLE {name} {add} {}",
+        column_definition(column, dialect)
+    ))];
+    if let (true, Some(comment)) = (separate_comments(dialect), &column.comment) {
+        changes.push(comment_on_column(&name, column, Some(comment), dialect));
+    }
+
+    changes
+}
+
+/// Adds `constraint` to `table`, which SQLite can only do by rebuilding it.
+pub fn add_constraint(table: &Table, constraint: &Constraint, dialect: Dialect) -> Change {
+    let name = dialect.quote_qualified(&table.qualified_name());
+    let definition = constraint_definition(constraint, dialect);
+    match dialect {
+        Dialect::Sqlite => Change::Note(format!(
+            "SQLite can't add `{definition}` to {name}; rebuild the table to add it"
+        )),
+        _ => Change::Sql(format!("ALTER TABLE {name} ADD {definition}")),
+    }
+}
+
+/// Whether the dialect sets comments with `COMMENT ON` rather than in the
+/// definition.
+fn separate_comments(dialect: Dialect) -> bool {
+    matches!(dialect, Dialect::Generic | Dialect::Postgres)
+}
+
+fn comment_on_column(
+    table: &str,
+    column: &Column,
+    comment: Option<&str>,
+    dialect: Dialect,
+) -> Change {
+    Change::Sql(format!(
+        "COMMENT ON COLUMN {table}.{} IS {}",
+        dialect.quote_ident(&column.name),
+        comment.map_or("NULL".to_string(), string)
+    ))
+}
+
+/// `value` as an SQL string literal.
+fn string(value: &str) -> String {
+    Value::SingleQuotedString(value.to_string()).to_string()
+}
+
+/// The changes to a table that exists in both schemas.
+struct Alter<'a> {
+    old: &'a Table,
+    new: &'a Table,
+    dialect: Dialect,
+    table: String,
+}
+
+impl<'a> Alter<'a> {
+    fn new(old: &'a Table, new: &'a Table, dialect: Dialect) -> Self {
+        Self {
+            old,
+            new,
+            dialect,
+            table: dialect.quote_qualified(&new.qualified_name()),
+        }
+    }
+
+    /// Removed constraints and indexes are dropped before columns change,
+    /// and added ones are created once the columns they cover exist.
+    fn changes(&self) -> Vec<Change> {
+        let mut changes = vec![];
+
+        for constraint in &self.old.constraints {
+            if !self.new.constraints.contains(constraint) {
+                changes.push(self.drop_constraint(constraint));
+            }
+        }
+        for index in &self.old.indexes {
+            if !self.new.indexes.contains(index) {
+                changes.push(self.drop_index(index));
+            }
+        }
+
+        for column in &self.new.columns {
+            match self.old.columns.iter().find(|old| old.name == column.name) {
+                Some(old) => changes.extend(self.alter_column(old, column)),
+                None => changes.extend(add_column(self.new, column, self.dialect)),
+            }
+        }
+
+        for column in &self.old.columns {
+            if !self.new.columns.iter().any(|new| new.name == column.name) {
+                changes.push(Change::Sql(format!(
+                    "ALTER TABLE {} DROP COLUMN {}",
+                    self.table,
+                    self.dialect.quote_ident(&column.name)
+                )));
+            }
+        }
+
+        for constraint in &self.new.constraints {
+            if !self.old.constraints.contains(constraint) {
+                changes.push(add_constraint(self.new, constraint, self.dialect));
+            }
+        }
+        for index in &self.new.indexes {
+            if !self.old.indexes.contains(index) {
+                changes.push(create_index(self.new, index, self.dialect));
+            }
+        }
+
+        if self.old.comment != self.new.comment {
+            changes.extend(self.comment_table());
+        }
+
+        changes
+    }
+
+    fn alter_column(&self, old: &Column, new: &Column) -> Vec<Change> {
+        let type_changed = !old
+            .data_type
+            .to_string()
+            .eq_ignore_ascii_case(&new.data_type.to_string());
+        let null_changed = old.nullable != new.nullable;
+        let default_changed = old.default.as_ref().map(ToString::to_string)
+            != new.default.as_ref().map(ToString::to_string);
+        let options_changed = old.options != new.options;
+        let comment_changed = old.comment != new.comment;
+
+        let definition_changed = type_changed || null_changed || default_changed || options_changed;
+        if !(definition_changed || comment_changed) {
+            return vec![];
+        }
+
+        let column = self.dialect.quote_ident(&new.name);
+        let alter = format!("ALTER TABLE {} ALTER COLUMN {column}", self.table);
+        let mut changes = vec![];
+
+        match self.dialect {
+            Dialect::Sqlite if definition_changed => {
+                return vec![Change::Note(format!(
+                    "SQLite can't alter column {}.{}; rebuild the table to change it to `{}`",
+                    self.table,
+                    column,
+                    column_definition(new, self.dialect)
+                ))];
+            }
+            Dialect::Sqlite => return vec![],
+            // MODIFY restates the whole definition, options and comment
+            // included.
+            Dialect::MySql => {
+                return vec![Change::Sql(format!(
+                    "ALTER TABLE {} MODIFY COLUMN {}",
+                    self.table,
+                    column_definition(new, self.dialect)
+                ))];
+            }
+            Dialect::MsSql => {
+                if type_changed || null_changed {
+                    let null = if new.nullable { "NULL" } else { "NOT NULL" };
+                    changes.push(Change::Sql(format!("{alter} {} {null}", new.data_type)));
+                }
+                if default_changed {
+                    if old.default.is_some() {
+                        changes.push(Change::Note(format!(
+                            "drop the default constraint on {}.{column} before changing its default",
+                            self.table
+                        )));
+                    }
+                    if let Some(default) = &new.default {
+                        changes.push(Change::Sql(format!(
+                            "ALTER TABLE {} ADD DEFAULT {default} FOR {column}",
+                            self.table
+                        )));
+                    }
+                }
+            }
+            Dialect::Generic | Dialect::Postgres => {
+                if type_changed {
+                    let keyword = match self.dialect {
+                        Dialect::Postgres => "TYPE",
+                        _ => "SET DATA TYPE",
+                    };
+                    changes.push(Change::Sql(format!("{alter} {keyword} {}", new.data_type)));
+                }
+                if null_changed {
+                    let action = if new.nullable { "DROP" } else { "SET" };
+                    changes.push(Change::Sql(format!("{alter} {action} NOT NULL")));
+                }
+                if default_changed {
+                    match &new.default {
+                        Some(default) => {
+                            changes.push(Change::Sql(format!("{alter} SET DEFAULT {default}")))
+                        }
+                        None => changes.push(Change::Sql(format!("{alter} DROP DEFAULT"))),
+                    }
+                }
+                if comment_changed {
+                    changes.push(comment_on_column(
+                        &self.table,
+                        new,
+                        new.comment.as_deref(),
+                        self.dialect,
+                    ));
+                }
+            }
+        }
+
+        if options_changed {
+            changes.push(Change::Note(format!(
+                "the options of {}.{column} changed from `{}` to `{}`; update them by hand",
+                self.table,
+                old.options.join(" "),
+                new.options.join(" ")
+            )));
+        }
+
+        changes
+    }
+
+    fn comment_table(&self) -> Option<Change> {
+        let comment = self.new.comment.as_deref();
+        match self.dialect {
+            Dialect::MySql => Some(Change::Sql(format!(
+                "ALTER TABLE {} COMMENT = {}",
+                self.table,
+                string(comment.unwrap_or(""))
+            ))),
+            Dialect::Generic | Dialect::Postgres => Some(Change::Sql(format!(
+                "COMMENT ON TABLE {} IS {}",
+                self.table,
+                comment.map_or("NULL".to_string(), string)
+            ))),
+            Dialect::Sqlite | Dialect::MsSql => None,
+        }
+    }
+
+    fn drop_constraint(&self, constraint: &Constraint) -> Change {
+        if let (Dialect::MySql, Constraint::PrimaryKey { .. }) = (self.dialect, constraint) {
+            return Change::Sql(format!("ALTER TABLE {} DROP PRIMARY KEY", self.table));
+        }
+
+        let definition = constraint_definition(constraint, self.dialect);
+        if self.dialect == Dialect::Sqlite {
+            return Change::Note(format!(
+                "SQLite can't drop `{definition}` from {}; rebuild the table to remove it",
+                self.table
+            ));
+        }
+        let name = match constraint {
+            Constraint::PrimaryKey { name, .. }
+            | Constraint::Unique { name, .. }
+            | Constraint::Check { name, .. } => name,
+            Constraint::ForeignKey(key) => &key.name,
+        };
+        let Some(name) = name else {
+            return Change::Note(format!(
+                "drop `{definition}` from {} by hand; it has no name to refer to",
+                self.table
+            ));
+        };
+
+        let name = self.dialect.quote_ident(name);
+        let drop = match (self.dialect, constraint) {
+            (Dialect::MySql, Constraint::ForeignKey(_)) => format!("DROP FOREIGN KEY {name}"),
+            (Dialect::MySql, Constraint::Check { .. }) => format!("DROP CHECK {name}"),
+            (Dialect::MySql, Constraint::Unique { .. }) => format!("DROP INDEX {name}"),
+            _ => format!("DROP CONSTRAINT {name}"),
+        };
+
+        Change::Sql(format!("ALTER TABLE {} {drop}", self.table))
+    }
+
+    fn drop_index(&self, index: &Index) -> Change {
+        let Some(name) = &index.name else {
+            return Change::Note(format!(
+                "drop the index on ({}) from {} by hand; it has no name to refer to",
+                index.columns.join(", "),
+                self.table
+            ));
+        };
+
+        Change::Sql(match self.dialect {
+            Dialect::MySql | Dialect::MsSql => format!(
+                "DROP INDEX {} ON {}",
+                self.dialect.quote_ident(name),
+                self.table
+            ),
+            // The index lives in its table's schema.
+            _ => {
+                let mut parts = self.new.schema.clone();
+                parts.push(name.clone());
+                format!("DROP INDEX {}", self.dialect.quote_qualified(&parts))
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tables(files: &[&str]) -> Vec<Table> {
+        files
+            .iter()
+            .map(|sql| crate::lang::testing::table(sql))
+            .collect()
+    }
+
+    fn sql(changes: Vec<Change>) -> Vec<String> {
+        changes
+            .into_iter()
+            .map(|change| match change {
+                Change::Sql(sql) => sql,
+                Change::Note(note) => format!("-- TODO: {note}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn creates_and_drops_in_dependency_order() {
+        let posts = "CREATE TABLE posts (id INT PRIMARY KEY, user_id INT REFERENCES users (id));";
+        let users = "CREATE TABLE users (id INT PRIMARY KEY);";
+
+        assert_eq!(
+            sql(diff(&[], &tables(&[posts, users]), Dialect::Postgres)),
+            [
+                r#"CREATE TABLE "users" ("id" INT NOT NULL, PRIMARY KEY ("id"))"#,
+                r#"CREATE TABLE "posts" ("id" INT NOT NULL, "user_id" INT, PRIMARY KEY ("id"), FOREIGN KEY ("user_id") REFERENCES "users" ("id"))"#,
+            ]
+        );
+        assert_eq!(
+            sql(diff(&tables(&[posts, users]), &[], Dialect::Postgres)),
+            [r#"DROP TABLE "posts""#, r#"DROP TABLE "users""#]
+        );
+    }
+
+    #[test]
+    fn alters_columns() {
+        let old = tables(&["CREATE TABLE users (id INT, name VARCHAR(50), age INT);"]);
+        let new = tables(&[
+            "CREATE TABLE users (id INT, name VARCHAR(100) NOT NULL DEFAULT '', email TEXT);
+            COMMENT ON COLUMN users.email IS 'Login';",
+        ]);
+
+        assert_eq!(
+            sql(diff(&old, &new, Dialect::Postgres)),
+            [
+                r#"ALTER TABLE "users" ALTER COLUMN "name" TYPE VARCHAR(100)"#,
+                r#"ALTER TABLE "users" ALTER COLUMN "name" SET NOT NULL"#,
+                r#"ALTER TABLE "users" ALTER COLUMN "name" SET DEFAULT ''"#,
+                r#"ALTER TABLE "users" ADD COLUMN "email" TEXT"#,
+                r#"COMMENT ON COLUMN "users"."email" IS 'Login'"#,
+                r#"ALTER TABLE "users" DROP COLUMN "age""#,
+            ]
+        );
+        assert_eq!(
+            sql(diff(&old, &new, Dialect::MySql)),
+            [
+                "ALTER TABLE `users` MODIFY COLUMN `name` VARCHAR(100) NOT NULL DEFAULT ''",
+                "ALTER TABLE `users` ADD COLUMN `email` TEXT COMMENT 'Login'",
+                "ALTER TABLE `users` DROP COLUMN `age`",
+            ]
+        );
+    }
+
+    #[test]
+    fn constraints_and_indexes() {
+        let old = tables(&[
+            "CREATE TABLE users (id INT PRIMARY KEY, email TEXT, CONSTRAINT users_email UNIQUE (email));
+            CREATE INDEX users_id ON users (id);",
+        ]);
+        let new = tables(&[
+            "CREATE TABLE users (id INT PRIMARY KEY, email TEXT, CHECK (email <> ''));
+            CREATE UNIQUE INDEX users_lower_email ON users (lower(email));",
+        ]);
+
+        assert_eq!(
+            sql(diff(&old, &new, Dialect::MySql)),
+            [
+                "ALTER TABLE `users` DROP INDEX `users_email`",
+                "DROP INDEX `users_id` ON `users`",
+                "ALTER TABLE `users` ADD CHECK (email <> '')",
+                "ALTER TABLE `users` ADD UNIQUE INDEX `users_lower_email` (lower(email))",
+            ]
+        );
+        assert_eq!(
+            sql(diff(&old, &new, Dialect::Sqlite)),
+            [
+                r#"-- TODO: SQLite can't drop `CONSTRAINT "users_email" UNIQUE ("email")` from "users"; rebuild the table to remove it"#,
+                r#"DROP INDEX "users_id""#,
+                r#"-- TODO: SQLite can't add `CHECK (email <> '')` to "users"; rebuild the table to add it"#,
+                r#"CREATE UNIQUE INDEX "users_lower_email" ON "users" (lower(email))"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn snapshots_round_trip() {
+        let snapshot = Snapshot::new(tables(&["CREATE TABLE users (
+                id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+                email VARCHAR(100) NOT NULL DEFAULT '' COMMENT 'Login',
+                created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+                CONSTRAINT users_email CHECK (email <> '' AND email LIKE '%@%')
+            );
+            CREATE INDEX users_created_at ON users (created_at);"]));
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let loaded: Snapshot = serde_json::from_str(&json).unwrap();
+
+        assert!(diff(&snapshot.tables, &loaded.tables, Dialect::MySql).is_empty());
+        assert_eq!(
+            loaded.tables[0].columns[0].sql_type,
+            ir::SqlType::Integer {
+                bytes: 8,
+                unsigned: true
+            }
+        );
+    }
+
+    #[test]
+    fn sqlite_keeps_autoincrement_on_the_key() {
+        let new =
+            tables(&["CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT);"]);
+
+        assert_eq!(
+            sql(diff(&[], &new, Dialect::Sqlite)),
+            [
+                r#"CREATE TABLE "users" ("id" INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT, "name" TEXT)"#
+            ]
+        );
+    }
+}
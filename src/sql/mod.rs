@@ -1,17 +1,51 @@
+pub mod dialect;
+pub mod ir;
+pub mod migrate;
+pub mod query;
 pub mod schema;
 
-use anyhow::anyhow;
+use anyhow::{Context, anyhow};
 use colored::Colorize;
 use rayon::prelude::*;
 use sqlparser::parser::Parser;
 use std::{fs, path::Path};
 use walkdir::WalkDir;
 
+/// Builds the table declared by each file in `dir`, as `(stem, table)` pairs
+/// ordered by file name. Every file must be an `.sql` file.
+pub fn load_tables<P: AsRef<Path>>(dir: P) -> anyhow::Result<Vec<(String, ir::Table)>> {
+    let mut tables = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let stem = match path.extension() {
+            Some(ext) if ext == "sql" => path.file_stem().and_then(|stem| stem.to_str()),
+            _ => None,
+        };
+        let Some(stem) = stem else {
+            return Err(anyhow!(
+                "file {} does not have a valid extension",
+                path.display()
+            ));
+        };
+
+        let table =
+            ir::Table::new(parse_file(&path)?).with_context(|| format!("in {}", path.display()))?;
+        tables.push((stem.to_string(), table));
+    }
+    tables.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    Ok(tables)
+}
+
 pub fn parse_file<P: AsRef<Path>>(file: P) -> anyhow::Result<Vec<sqlparser::ast::Statement>> {
     let sql = std::fs::read_to_string(file)?;
 
+    parse_sql(&sql)
+}
+
+pub fn parse_sql(sql: &str) -> anyhow::Result<Vec<sqlparser::ast::Statement>> {
     let dialect = sqlparser::dialect::GenericDialect {};
-    let ast = Parser::parse_sql(&dialect, &sql)?;
+    let ast = Parser::parse_sql(&dialect, sql)?;
 
     Ok(ast)
 }
@@ -24,7 +58,7 @@ pub fn fmt_recursively<P: AsRef<Path>>(dir: P, minify: bool) -> anyhow::Result<(
     let entries: Vec<walkdir::DirEntry> = WalkDir::new(dir)
         .into_iter()
         .filter_map(|e| {
-            if let Some(f) = e.ok() {
+            if let Ok(f) = e {
                 if f.path().extension().is_some_and(|f| f == "sql") {
                     return Some(f);
                 } else {
@@ -0,0 +1,119 @@
+use anyhow::anyhow;
+use sqlparser::ast::Statement;
+use std::{path::Path, str::FromStr};
+
+/// What a named query returns, taken from its `-- name: <Name> :<command>`
+/// annotation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryCommand {
+    /// A single row, or nothing.
+    One,
+    /// Every matching row.
+    Many,
+    /// Nothing.
+    Exec,
+    /// The number of affected rows.
+    ExecRows,
+    /// The ID of the inserted row.
+    ExecLastId,
+}
+
+impl FromStr for QueryCommand {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "one" => Ok(Self::One),
+            "many" => Ok(Self::Many),
+            "exec" => Ok(Self::Exec),
+            "execrows" => Ok(Self::ExecRows),
+            "execlastid" => Ok(Self::ExecLastId),
+            _ => Err(anyhow!("unknown query command: :{s}")),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct NamedQuery {
+    pub name: String,
+    pub command: QueryCommand,
+    pub statement: Statement,
+}
+
+/// Parses each query file in `dir`, if there is one, as `(stem, queries)`
+/// pairs ordered by file name.
+pub fn load_queries(dir: &Path) -> anyhow::Result<Vec<(String, Vec<NamedQuery>)>> {
+    let mut queries = vec![];
+    if !dir.is_dir() {
+        return Ok(queries);
+    }
+
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|ext| ext != "sql") {
+            continue;
+        }
+
+        let file_stem = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or("")
+            .to_string();
+        queries.push((file_stem, parse_queries(&path)?));
+    }
+    queries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    Ok(queries)
+}
+
+/// Parses a file of queries, each preceded by a sqlc-style annotation:
+///
+/// ```sql
+/// -- name: GetUserByEmail :one
+/// SELECT * FROM users WHERE email = ?;
+/// ```
+pub fn parse_queries<P: AsRef<Path>>(file: P) -> anyhow::Result<Vec<NamedQuery>> {
+    let sql = std::fs::read_to_string(&file)?;
+
+    let mut annotations = vec![];
+    for line in sql.lines() {
+        let Some(annotation) = line.trim().strip_prefix("--") else {
+            continue;
+        };
+        let Some(annotation) = annotation.trim().strip_prefix("name:") else {
+            continue;
+        };
+
+        let mut parts = annotation.split_whitespace();
+        let (Some(name), Some(command), None) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(anyhow!(
+                "invalid query annotation `{}`: expected `-- name: <Name> :<command>`",
+                line.trim()
+            ));
+        };
+        let Some(command) = command.strip_prefix(':') else {
+            return Err(anyhow!("invalid query command `{command}` for {name}"));
+        };
+
+        annotations.push((name.to_string(), QueryCommand::from_str(command)?));
+    }
+
+    let statements = super::parse_file(&file)?;
+    if statements.len() != annotations.len() {
+        return Err(anyhow!(
+            "{}: found {} statements but {} `-- name:` annotations; every query needs exactly one",
+            file.as_ref().display(),
+            statements.len(),
+            annotations.len()
+        ));
+    }
+
+    Ok(annotations
+        .into_iter()
+        .zip(statements)
+        .map(|((name, command), statement)| NamedQuery {
+            name,
+            command,
+            statement,
+        })
+        .collect())
+}
@@ -1,26 +1,24 @@
-use std::{fmt::Display, str::FromStr};
-
 use sqlparser::parser::Parser;
 
+use super::dialect::Dialect;
 
+#[derive(Default)]
 pub struct Formatter {
     pub minify: bool,
-}
-
-impl Default for Formatter {
-    fn default() -> Self {
-        Self { minify: false }
-    }
+    /// The dialect `run` parses its input with.
+    pub dialect: Dialect,
 }
 
 impl Formatter {
     pub fn new(minify: bool) -> Self {
-        Self { minify }
+        Self {
+            minify,
+            dialect: Dialect::Generic,
+        }
     }
 
     pub fn run(&self, sql: &str) -> anyhow::Result<String> {
-        let dialect = sqlparser::dialect::GenericDialect {};
-        let ast = Parser::parse_sql(&dialect, &sql)?;
+        let ast = Parser::parse_sql(self.dialect.parser().as_ref(), sql)?;
 
         let mut buffer = String::new();
         for (i, node) in ast.iter().enumerate() {
@@ -55,28 +53,3 @@ impl Formatter {
         Ok(buffer)
     }
 }
-
-#[derive(Debug, Clone, Copy)]
-pub enum RowCase {
-    CamelCase,
-    SnakeCase,
-    PascalCase,
-}
-
-impl Display for RowCase {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
-    }
-}
-
-impl FromStr for RowCase {
-    type Err = String;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            "camel" => Ok(Self::CamelCase),
-            "snake" => Ok(Self::SnakeCase),
-            "pascal" => Ok(Self::PascalCase),
-            _ => Err(format!("Unknown case: {s}")),
-        }
-    }
-}
\ No newline at end of file
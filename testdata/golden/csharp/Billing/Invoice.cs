@@ -0,0 +1,14 @@
+// Generated by quaff from billing.invoices.sql.
+
+#nullable enable
+
+using System;
+
+namespace Quaff.Models.Billing;
+
+public record Invoice(
+    int Id,
+    long? UserId,
+    decimal Total,
+    DateOnly IssuedOn
+);
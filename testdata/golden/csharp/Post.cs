@@ -0,0 +1,18 @@
+// Generated by quaff from posts.sql.
+
+#nullable enable
+
+using System;
+
+namespace Quaff.Models;
+
+public record Post(
+    int Id,
+    long AuthorId,
+    string Title,
+    string? Body,
+    string? Meta,
+    uint Views,
+    bool Published,
+    DateTime? PublishedAt
+);
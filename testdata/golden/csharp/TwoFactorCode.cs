@@ -0,0 +1,10 @@
+// Generated by quaff from 2fa-codes.sql.
+
+#nullable enable
+
+namespace Quaff.Models;
+
+public record TwoFactorCode(
+    string Code,
+    long UserId
+);
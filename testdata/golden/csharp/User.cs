@@ -0,0 +1,23 @@
+// Generated by quaff from users.sql.
+
+#nullable enable
+
+using System;
+
+namespace Quaff.Models;
+
+/// <summary>
+/// People who log in
+/// </summary>
+/// <param name="Email">Login e-mail, never */ shared</param>
+/// <param name="Class">Multi line "quoted" $x &lt;b&gt;</param>
+public record User(
+    long Id,
+    string Email,
+    string? Class,
+    decimal? Price,
+    string Status,
+    bool Active,
+    Guid? Token,
+    DateTime CreatedAt
+);
@@ -0,0 +1,35 @@
+<?php
+namespace Quaff;
+use Doctrine\DBAL\Types\Types;
+use Doctrine\ORM\Mapping as ORM;
+
+#[ORM\Entity]
+#[ORM\Table(name: 'two_factor_codes')]
+class TwoFactorCode {
+    #[ORM\Id]
+    #[ORM\Column(type: Types::STRING, length: 6, options: ['fixed' => true])]
+    private string $code;
+
+    #[ORM\Column(name: 'user_id', type: Types::BIGINT)]
+    private int $userId;
+
+    public function getCode(): string {
+        return $this->code;
+    }
+
+    public function setCode(string $code): static {
+        $this->code = $code;
+
+        return $this;
+    }
+
+    public function getUserId(): int {
+        return $this->userId;
+    }
+
+    public function setUserId(int $userId): static {
+        $this->userId = $userId;
+
+        return $this;
+    }
+}
\ No newline at end of file
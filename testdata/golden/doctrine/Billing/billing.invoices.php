@@ -0,0 +1,62 @@
+<?php
+namespace Quaff\Billing;
+use Doctrine\DBAL\Types\Types;
+use Doctrine\ORM\Mapping as ORM;
+
+#[ORM\Entity]
+#[ORM\Table(name: 'invoices', schema: 'billing')]
+class Invoice {
+    #[ORM\Id]
+    #[ORM\Column(type: Types::INTEGER)]
+    private int $id;
+
+    #[ORM\ManyToOne(targetEntity: \Quaff\User::class)]
+    #[ORM\JoinColumn(name: 'user_id', referencedColumnName: 'id')]
+    private ?\Quaff\User $user = null;
+
+    #[ORM\Column(type: Types::DECIMAL, precision: 10, scale: 2)]
+    private string $total;
+
+    #[ORM\Column(name: 'issued_on', type: Types::DATE_IMMUTABLE)]
+    private \DateTimeImmutable $issuedOn;
+
+    public function getId(): int {
+        return $this->id;
+    }
+
+    public function setId(int $id): static {
+        $this->id = $id;
+
+        return $this;
+    }
+
+    public function getUser(): ?\Quaff\User {
+        return $this->user;
+    }
+
+    public function setUser(?\Quaff\User $user): static {
+        $this->user = $user;
+
+        return $this;
+    }
+
+    public function getTotal(): string {
+        return $this->total;
+    }
+
+    public function setTotal(string $total): static {
+        $this->total = $total;
+
+        return $this;
+    }
+
+    public function getIssuedOn(): \DateTimeImmutable {
+        return $this->issuedOn;
+    }
+
+    public function setIssuedOn(\DateTimeImmutable $issuedOn): static {
+        $this->issuedOn = $issuedOn;
+
+        return $this;
+    }
+}
\ No newline at end of file
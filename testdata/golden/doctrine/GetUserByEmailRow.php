@@ -0,0 +1,38 @@
+<?php
+namespace Quaff;
+use DateTime;
+
+class GetUserByEmailRow {
+    public function __construct(
+        /** @var int BIGINT */
+        public readonly int $id,
+        /** @var string VARCHAR(100) */
+        public readonly string $email,
+        /** @var string|null TEXT */
+        public readonly ?string $class,
+        /** @var string|null DECIMAL(8,2) */
+        public readonly ?string $price,
+        /** @var string ENUM('active', 'banned') */
+        public readonly string $status,
+        /** @var bool BOOLEAN */
+        public readonly bool $active,
+        /** @var string|null UUID */
+        public readonly ?string $token,
+        /** @var DateTime TIMESTAMP */
+        public readonly DateTime $created_at
+    ) { }
+
+    /** @param array<string, mixed> $row */
+    public static function fromRow(array $row): self {
+        return new self(
+            id: (int) $row['id'],
+            email: $row['email'],
+            class: $row['class'],
+            price: $row['price'],
+            status: $row['status'],
+            active: (bool) $row['active'],
+            token: $row['token'],
+            created_at: new DateTime($row['created_at']),
+        );
+    }
+}
\ No newline at end of file
@@ -0,0 +1,47 @@
+<?php
+namespace Quaff;
+use DateTime;
+
+class UsersQueries {
+    public function __construct(private \PDO $pdo) { }
+
+    public function getUserByEmail(string $email): ?GetUserByEmailRow {
+        $stmt = $this->pdo->prepare('SELECT * FROM users WHERE email = ?');
+        $stmt->bindValue(1, $email, \PDO::PARAM_STR);
+        $stmt->execute();
+
+        $row = $stmt->fetch(\PDO::FETCH_ASSOC);
+
+        return $row === false ? null : GetUserByEmailRow::fromRow($row);
+    }
+
+    /** @return ListPostsRow[] */
+    public function listPosts(bool $published, int $limit): array {
+        $stmt = $this->pdo->prepare('SELECT p.id, p.title, u.email AS author_email FROM posts AS p JOIN users AS u ON u.id = p.author_id WHERE p.published = :published LIMIT :limit');
+        $stmt->bindValue(':published', $published, \PDO::PARAM_BOOL);
+        $stmt->bindValue(':limit', $limit, \PDO::PARAM_INT);
+        $stmt->execute();
+
+        return array_map(
+            fn (array $row): ListPostsRow => ListPostsRow::fromRow($row),
+            $stmt->fetchAll(\PDO::FETCH_ASSOC),
+        );
+    }
+
+    public function createUser(string $email, string $status): string|false {
+        $stmt = $this->pdo->prepare('INSERT INTO users (email, status) VALUES (?, ?)');
+        $stmt->bindValue(1, $email, \PDO::PARAM_STR);
+        $stmt->bindValue(2, $status, \PDO::PARAM_STR);
+        $stmt->execute();
+
+        return $this->pdo->lastInsertId();
+    }
+
+    public function deactivate(int $id): int {
+        $stmt = $this->pdo->prepare('UPDATE users SET active = false WHERE id = ?');
+        $stmt->bindValue(1, $id, \PDO::PARAM_INT);
+        $stmt->execute();
+
+        return $stmt->rowCount();
+    }
+}
\ No newline at end of file
@@ -0,0 +1,109 @@
+<?php
+namespace Quaff;
+use Doctrine\DBAL\Types\Types;
+use Doctrine\ORM\Mapping as ORM;
+
+#[ORM\Entity]
+#[ORM\Table(name: 'posts')]
+class Post {
+    #[ORM\Id]
+    #[ORM\GeneratedValue]
+    #[ORM\Column(type: Types::INTEGER)]
+    private ?int $id = null;
+
+    #[ORM\ManyToOne(targetEntity: User::class)]
+    #[ORM\JoinColumn(name: 'author_id', referencedColumnName: 'id', nullable: false)]
+    private User $author;
+
+    #[ORM\Column(type: Types::STRING, length: 200)]
+    private string $title;
+
+    #[ORM\Column(type: Types::TEXT, nullable: true)]
+    private ?string $body = null;
+
+    #[ORM\Column(type: Types::JSON, nullable: true)]
+    private ?array $meta = null;
+
+    #[ORM\Column(type: Types::INTEGER, options: ['unsigned' => true, 'default' => 0])]
+    private int $views = 0;
+
+    #[ORM\Column(type: Types::BOOLEAN, options: ['default' => false])]
+    private bool $published = false;
+
+    #[ORM\Column(name: 'published_at', type: Types::DATETIME_IMMUTABLE, nullable: true)]
+    private ?\DateTimeImmutable $publishedAt = null;
+
+    public function getId(): ?int {
+        return $this->id;
+    }
+
+    public function getAuthor(): User {
+        return $this->author;
+    }
+
+    public function setAuthor(User $author): static {
+        $this->author = $author;
+
+        return $this;
+    }
+
+    public function getTitle(): string {
+        return $this->title;
+    }
+
+    public function setTitle(string $title): static {
+        $this->title = $title;
+
+        return $this;
+    }
+
+    public function getBody(): ?string {
+        return $this->body;
+    }
+
+    public function setBody(?string $body): static {
+        $this->body = $body;
+
+        return $this;
+    }
+
+    public function getMeta(): ?array {
+        return $this->meta;
+    }
+
+    public function setMeta(?array $meta): static {
+        $this->meta = $meta;
+
+        return $this;
+    }
+
+    public function getViews(): int {
+        return $this->views;
+    }
+
+    public function setViews(int $views): static {
+        $this->views = $views;
+
+        return $this;
+    }
+
+    public function getPublished(): bool {
+        return $this->published;
+    }
+
+    public function setPublished(bool $published): static {
+        $this->published = $published;
+
+        return $this;
+    }
+
+    public function getPublishedAt(): ?\DateTimeImmutable {
+        return $this->publishedAt;
+    }
+
+    public function setPublishedAt(?\DateTimeImmutable $publishedAt): static {
+        $this->publishedAt = $publishedAt;
+
+        return $this;
+    }
+}
\ No newline at end of file
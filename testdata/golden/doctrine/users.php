@@ -0,0 +1,119 @@
+<?php
+namespace Quaff;
+use Doctrine\DBAL\Types\Types;
+use Doctrine\ORM\Mapping as ORM;
+
+enum UserStatus: string {
+    case Active = 'active';
+    case Banned = 'banned';
+}
+
+/** People who log in */
+#[ORM\Entity]
+#[ORM\Table(name: 'users')]
+class User {
+    #[ORM\Id]
+    #[ORM\GeneratedValue]
+    #[ORM\Column(type: Types::BIGINT)]
+    private ?int $id = null;
+
+    /** Login e-mail, never *\/ shared */
+    #[ORM\Column(type: Types::STRING, length: 100, unique: true)]
+    private string $email;
+
+    /**
+     * Multi
+     * line "quoted" $x <b>
+     */
+    #[ORM\Column(type: Types::TEXT, nullable: true)]
+    private ?string $class = null;
+
+    #[ORM\Column(type: Types::DECIMAL, precision: 8, scale: 2, nullable: true)]
+    private ?string $price = null;
+
+    #[ORM\Column(type: Types::STRING, enumType: UserStatus::class, options: ['default' => 'active'])]
+    private UserStatus $status = UserStatus::Active;
+
+    #[ORM\Column(type: Types::BOOLEAN, options: ['default' => true])]
+    private bool $active = true;
+
+    #[ORM\Column(type: Types::GUID, nullable: true)]
+    private ?string $token = null;
+
+    #[ORM\Column(name: 'created_at', type: Types::DATETIME_IMMUTABLE)]
+    private \DateTimeImmutable $createdAt;
+
+    public function getId(): ?int {
+        return $this->id;
+    }
+
+    public function getEmail(): string {
+        return $this->email;
+    }
+
+    public function setEmail(string $email): static {
+        $this->email = $email;
+
+        return $this;
+    }
+
+    public function getClass(): ?string {
+        return $this->class;
+    }
+
+    public function setClass(?string $class): static {
+        $this->class = $class;
+
+        return $this;
+    }
+
+    public function getPrice(): ?string {
+        return $this->price;
+    }
+
+    public function setPrice(?string $price): static {
+        $this->price = $price;
+
+        return $this;
+    }
+
+    public function getStatus(): UserStatus {
+        return $this->status;
+    }
+
+    public function setStatus(UserStatus $status): static {
+        $this->status = $status;
+
+        return $this;
+    }
+
+    public function getActive(): bool {
+        return $this->active;
+    }
+
+    public function setActive(bool $active): static {
+        $this->active = $active;
+
+        return $this;
+    }
+
+    public function getToken(): ?string {
+        return $this->token;
+    }
+
+    public function setToken(?string $token): static {
+        $this->token = $token;
+
+        return $this;
+    }
+
+    public function getCreatedAt(): \DateTimeImmutable {
+        return $this->createdAt;
+    }
+
+    public function setCreatedAt(\DateTimeImmutable $createdAt): static {
+        $this->createdAt = $createdAt;
+
+        return $this;
+    }
+}
\ No newline at end of file
@@ -0,0 +1,26 @@
+<?php
+namespace Quaff;
+use Illuminate\Database\Eloquent\Model;
+
+/**
+ * @property string $code
+ * @property int $user_id
+ */
+class TwoFactorCode extends Model {
+    protected $table = 'two_factor_codes';
+    protected $primaryKey = 'code';
+    public $incrementing = false;
+    protected $keyType = 'string';
+    public $timestamps = false;
+
+    /** @var list<string> */
+    protected $fillable = [
+        'code',
+        'user_id',
+    ];
+
+    /** @param array<string, mixed> $row */
+    public static function fromRow(array $row): self {
+        return (new self())->newFromBuilder($row);
+    }
+}
\ No newline at end of file
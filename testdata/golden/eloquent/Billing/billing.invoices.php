@@ -0,0 +1,40 @@
+<?php
+namespace Quaff\Billing;
+use Illuminate\Database\Eloquent\Model;
+use Illuminate\Database\Eloquent\Relations\BelongsTo;
+
+/**
+ * @property int $id
+ * @property ?int $user_id
+ * @property string $total
+ * @property \Illuminate\Support\Carbon $issued_on
+ */
+class Invoice extends Model {
+    protected $table = 'billing.invoices';
+    protected $primaryKey = 'id';
+    public $incrementing = false;
+    public $timestamps = false;
+
+    /** @var list<string> */
+    protected $fillable = [
+        'id',
+        'user_id',
+        'total',
+        'issued_on',
+    ];
+
+    /** @var array<string, string> */
+    protected $casts = [
+        'total' => 'decimal:2',
+        'issued_on' => 'date',
+    ];
+
+    /** @param array<string, mixed> $row */
+    public static function fromRow(array $row): self {
+        return (new self())->newFromBuilder($row);
+    }
+
+    public function user(): BelongsTo {
+        return $this->belongsTo(\Quaff\User::class, 'user_id', 'id');
+    }
+}
\ No newline at end of file
@@ -0,0 +1,23 @@
+<?php
+namespace Quaff;
+use DateTime;
+
+class ListPostsRow {
+    public function __construct(
+        /** @var int INT */
+        public readonly int $id,
+        /** @var string VARCHAR(200) */
+        public readonly string $title,
+        /** @var string VARCHAR(100) */
+        public readonly string $author_email
+    ) { }
+
+    /** @param array<string, mixed> $row */
+    public static function fromRow(array $row): self {
+        return new self(
+            id: (int) $row['id'],
+            title: $row['title'],
+            author_email: $row['author_email'],
+        );
+    }
+}
\ No newline at end of file
@@ -0,0 +1,47 @@
+<?php
+namespace Quaff;
+use Illuminate\Database\Eloquent\Model;
+use Illuminate\Database\Eloquent\Relations\BelongsTo;
+
+/**
+ * @property-read int $id
+ * @property int $author_id
+ * @property string $title
+ * @property ?string $body
+ * @property ?array $meta
+ * @property int $views
+ * @property bool $published
+ * @property ?\Illuminate\Support\Carbon $published_at
+ */
+class Post extends Model {
+    protected $table = 'posts';
+    protected $primaryKey = 'id';
+    public $timestamps = false;
+
+    /** @var list<string> */
+    protected $fillable = [
+        'author_id',
+        'title',
+        'body',
+        'meta',
+        'views',
+        'published',
+        'published_at',
+    ];
+
+    /** @var array<string, string> */
+    protected $casts = [
+        'meta' => 'array',
+        'published' => 'boolean',
+        'published_at' => 'datetime',
+    ];
+
+    /** @param array<string, mixed> $row */
+    public static function fromRow(array $row): self {
+        return (new self())->newFromBuilder($row);
+    }
+
+    public function author(): BelongsTo {
+        return $this->belongsTo(User::class, 'author_id', 'id');
+    }
+}
\ No newline at end of file
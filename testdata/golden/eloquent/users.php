@@ -0,0 +1,50 @@
+<?php
+namespace Quaff;
+use Illuminate\Database\Eloquent\Model;
+
+enum UserStatus: string {
+    case Active = 'active';
+    case Banned = 'banned';
+}
+
+/**
+ * People who log in
+ *
+ * @property-read int $id
+ * @property string $email
+ * @property ?string $class
+ * @property ?string $price
+ * @property UserStatus $status
+ * @property bool $active
+ * @property ?string $token
+ * @property \Illuminate\Support\Carbon $created_at
+ */
+class User extends Model {
+    protected $table = 'users';
+    protected $primaryKey = 'id';
+    public $timestamps = false;
+
+    /** @var list<string> */
+    protected $fillable = [
+        'email',
+        'class',
+        'price',
+        'status',
+        'active',
+        'token',
+        'created_at',
+    ];
+
+    /** @var array<string, string> */
+    protected $casts = [
+        'price' => 'decimal:2',
+        'status' => UserStatus::class,
+        'active' => 'boolean',
+        'created_at' => 'datetime',
+    ];
+
+    /** @param array<string, mixed> $row */
+    public static function fromRow(array $row): self {
+        return (new self())->newFromBuilder($row);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,8 @@
+// Code generated by quaff from 2fa-codes.sql. DO NOT EDIT.
+
+package models
+
+type TwoFactorCode struct {
+	Code   string `db:"code" json:"code"`
+	UserID int64  `db:"user_id" json:"user_id"`
+}
@@ -0,0 +1,12 @@
+// Code generated by quaff from billing.invoices.sql. DO NOT EDIT.
+
+package billing
+
+import "time"
+
+type Invoice struct {
+	ID       int32     `db:"id" json:"id"`
+	UserID   *int64    `db:"user_id" json:"user_id"`
+	Total    string    `db:"total" json:"total"`
+	IssuedOn time.Time `db:"issued_on" json:"issued_on"`
+}
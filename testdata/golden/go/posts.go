@@ -0,0 +1,19 @@
+// Code generated by quaff from posts.sql. DO NOT EDIT.
+
+package models
+
+import (
+	"encoding/json"
+	"time"
+)
+
+type Post struct {
+	ID          int32           `db:"id" json:"id"`
+	AuthorID    int64           `db:"author_id" json:"author_id"`
+	Title       string          `db:"title" json:"title"`
+	Body        *string         `db:"body" json:"body"`
+	Meta        json.RawMessage `db:"meta" json:"meta"`
+	Views       uint32          `db:"views" json:"views"`
+	Published   bool            `db:"published" json:"published"`
+	PublishedAt *time.Time      `db:"published_at" json:"published_at"`
+}
@@ -0,0 +1,20 @@
+// Code generated by quaff from users.sql. DO NOT EDIT.
+
+package models
+
+import "time"
+
+// People who log in
+type User struct {
+	ID int64 `db:"id" json:"id"`
+	// Login e-mail, never */ shared
+	Email string `db:"email" json:"email"`
+	// Multi
+	// line "quoted" $x <b>
+	Class     *string   `db:"class" json:"class"`
+	Price     *string   `db:"price" json:"price"`
+	Status    string    `db:"status" json:"status"`
+	Active    bool      `db:"active" json:"active"`
+	Token     *string   `db:"token" json:"token"`
+	CreatedAt time.Time `db:"created_at" json:"created_at"`
+}
@@ -0,0 +1,38 @@
+// Generated by quaff from 2fa-codes.sql.
+
+package models;
+
+import jakarta.persistence.Column;
+import jakarta.persistence.Entity;
+import jakarta.persistence.Id;
+import jakarta.persistence.Table;
+
+@Entity
+@Table(name = "two_factor_codes")
+public class TwoFactorCode {
+    @Id
+    @Column(name = "code", nullable = false, length = 6)
+    private String code;
+
+    @Column(name = "user_id", nullable = false)
+    private long userId;
+
+    protected TwoFactorCode() {
+    }
+
+    public String getCode() {
+        return code;
+    }
+
+    public void setCode(String code) {
+        this.code = code;
+    }
+
+    public long getUserId() {
+        return userId;
+    }
+
+    public void setUserId(long userId) {
+        this.userId = userId;
+    }
+}
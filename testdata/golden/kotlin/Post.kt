@@ -0,0 +1,16 @@
+// Generated by quaff from posts.sql.
+
+package models
+
+import java.time.LocalDateTime
+
+data class Post(
+    val id: Int? = null,
+    val authorId: Long,
+    val title: String,
+    val body: String?,
+    val meta: String?,
+    val views: Long,
+    val published: Boolean,
+    val publishedAt: LocalDateTime?,
+)
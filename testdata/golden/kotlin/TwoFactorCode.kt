@@ -0,0 +1,8 @@
+// Generated by quaff from 2fa-codes.sql.
+
+package models
+
+data class TwoFactorCode(
+    val code: String,
+    val userId: Long,
+)
@@ -0,0 +1,24 @@
+// Generated by quaff from users.sql.
+
+package models
+
+import java.math.BigDecimal
+import java.time.LocalDateTime
+import java.util.UUID
+
+/** People who log in */
+data class User(
+    val id: Long? = null,
+    /** Login e-mail, never *\/ shared */
+    val email: String,
+    /**
+     * Multi
+     * line "quoted" $x <b>
+     */
+    val `class`: String?,
+    val price: BigDecimal?,
+    val status: String,
+    val active: Boolean,
+    val token: UUID?,
+    val createdAt: LocalDateTime,
+)
@@ -0,0 +1,13 @@
+// Generated by quaff from billing.invoices.sql.
+
+package models.billing
+
+import java.math.BigDecimal
+import java.time.LocalDate
+
+data class Invoice(
+    val id: Int,
+    val userId: Long?,
+    val total: BigDecimal,
+    val issuedOn: LocalDate,
+)
@@ -0,0 +1,28 @@
+<?php
+
+use Illuminate\Database\Migrations\Migration;
+use Illuminate\Database\Schema\Blueprint;
+use Illuminate\Support\Facades\Schema;
+
+return new class extends Migration {
+    public function up(): void {
+        Schema::create('posts', function (Blueprint $table) {
+            $table->id();
+            $table->integer('user_id');
+            $table->string('title', 200)->default('')->comment('Headline');
+            $table->mediumText('body')->nullable();
+            $table->decimal('price', 8, 2)->nullable()->default(-1.5);
+            $table->boolean('published')->default(false);
+            $table->enum('status', ['draft', 'live']);
+            $table->timestamp('created_at')->useCurrent();
+            $table->unique(['user_id', 'title'], 'posts_title');
+            $table->index(['created_at'], 'posts_created_at');
+            $table->foreign('user_id', 'posts_user')->references('id')->on('users')->onDelete('cascade');
+            $table->comment('Blog posts');
+        });
+    }
+
+    public function down(): void {
+        Schema::dropIfExists('posts');
+    }
+};
@@ -0,0 +1,27 @@
+<?php
+
+use Illuminate\Database\Migrations\Migration;
+use Illuminate\Database\Schema\Blueprint;
+use Illuminate\Support\Facades\DB;
+use Illuminate\Support\Facades\Schema;
+
+return new class extends Migration {
+    public function up(): void {
+        Schema::create('users', function (Blueprint $table) {
+            $table->integer('id')->primary();
+            $table->string('email', 100)->unique();
+            $table->integer('balance');
+            $table->uuid('token')->nullable()->default(DB::raw('gen_random_uuid()'));
+        });
+
+        // The schema builder can't express these, so they're added with SQL.
+        DB::statement('ALTER TABLE "users" ADD COLUMN "tags" TEXT[]');
+        DB::statement('ALTER TABLE "users" ADD COLUMN "email_domain" TEXT GENERATED ALWAYS AS (split_part(email, \'@\', 2)) STORED');
+        DB::statement('ALTER TABLE "users" ADD CHECK (balance >= 0)');
+        DB::statement('CREATE INDEX "users_lower_email" ON "users" (lower(email))');
+    }
+
+    public function down(): void {
+        Schema::dropIfExists('users');
+    }
+};
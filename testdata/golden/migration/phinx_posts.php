@@ -0,0 +1,27 @@
+<?php
+
+declare(strict_types=1);
+
+use Phinx\Migration\AbstractMigration;
+
+final class CreatePostsTable extends AbstractMigration {
+    public function up(): void {
+        $this->table('posts', ['id' => false, 'primary_key' => ['id'], 'comment' => 'Blog posts'])
+            ->addColumn('id', 'biginteger', ['identity' => true, 'signed' => false])
+            ->addColumn('user_id', 'integer')
+            ->addColumn('title', 'string', ['limit' => 200, 'default' => '', 'comment' => 'Headline'])
+            ->addColumn('body', 'text', ['null' => true])
+            ->addColumn('price', 'decimal', ['precision' => 8, 'scale' => 2, 'null' => true, 'default' => -1.5])
+            ->addColumn('published', 'boolean', ['default' => false])
+            ->addColumn('status', 'enum', ['values' => ['draft', 'live']])
+            ->addColumn('created_at', 'timestamp', ['default' => 'CURRENT_TIMESTAMP'])
+            ->addIndex(['user_id', 'title'], ['unique' => true, 'name' => 'posts_title'])
+            ->addIndex(['created_at'], ['name' => 'posts_created_at'])
+            ->addForeignKey('user_id', 'users', 'id', ['delete' => 'CASCADE', 'constraint' => 'posts_user'])
+            ->create();
+    }
+
+    public function down(): void {
+        $this->table('posts')->drop()->save();
+    }
+}
@@ -0,0 +1,28 @@
+<?php
+
+declare(strict_types=1);
+
+use Phinx\Migration\AbstractMigration;
+use Phinx\Util\Literal;
+
+final class CreateUsersTable extends AbstractMigration {
+    public function up(): void {
+        $this->table('users', ['id' => false, 'primary_key' => ['id']])
+            ->addColumn('id', 'integer')
+            ->addColumn('email', 'string', ['limit' => 100])
+            ->addColumn('balance', 'integer')
+            ->addColumn('token', 'uuid', ['null' => true, 'default' => Literal::from('gen_random_uuid()')])
+            ->addIndex(['email'], ['unique' => true])
+            ->create();
+
+        // The table builder can't express these, so they're added with SQL.
+        $this->execute('ALTER TABLE "users" ADD COLUMN "tags" TEXT[]');
+        $this->execute('ALTER TABLE "users" ADD COLUMN "email_domain" TEXT GENERATED ALWAYS AS (split_part(email, \'@\', 2)) STORED');
+        $this->execute('ALTER TABLE "users" ADD CHECK (balance >= 0)');
+        $this->execute('CREATE INDEX "users_lower_email" ON "users" (lower(email))');
+    }
+
+    public function down(): void {
+        $this->table('users')->drop()->save();
+    }
+}
@@ -0,0 +1,37 @@
+<?php
+namespace Quaff;
+use DateTime;
+
+class TwoFactorCode {
+    public function __construct(
+        /** @var string CHAR(6) */
+        private string $code,
+        /** @var int BIGINT */
+        public int $user_id
+    ) {
+        if (mb_strlen($this->code) > 6) {
+            throw new \InvalidArgumentException('code must be at most 6 characters');
+        }
+    }
+
+    /** @param array<string, mixed> $row */
+    public static function fromRow(array $row): self {
+        return new self(
+            code: $row['code'],
+            user_id: (int) $row['user_id'],
+        );
+    }
+
+    public function getCode(): string {
+        return $this->code;
+    }
+
+    public function toInsertArray(): array {
+        $row = [
+            'code' => $this->code,
+            'user_id' => $this->user_id,
+        ];
+
+        return $row;
+    }
+}
\ No newline at end of file
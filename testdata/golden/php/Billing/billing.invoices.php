@@ -0,0 +1,41 @@
+<?php
+namespace Quaff\Billing;
+use DateTime;
+
+class Invoice {
+    public function __construct(
+        /** @var int INT */
+        private int $id,
+        /** @var int|null BIGINT */
+        public ?int $user_id,
+        /** @var string DECIMAL(10,2) */
+        public string $total,
+        /** @var DateTime DATE */
+        public DateTime $issued_on
+    ) { }
+
+    /** @param array<string, mixed> $row */
+    public static function fromRow(array $row): self {
+        return new self(
+            id: (int) $row['id'],
+            user_id: $row['user_id'] === null ? null : (int) $row['user_id'],
+            total: $row['total'],
+            issued_on: new DateTime($row['issued_on']),
+        );
+    }
+
+    public function getId(): int {
+        return $this->id;
+    }
+
+    public function toInsertArray(): array {
+        $row = [
+            'id' => $this->id,
+            'user_id' => $this->user_id,
+            'total' => $this->total,
+            'issued_on' => $this->issued_on->format('Y-m-d H:i:s'),
+        ];
+
+        return $row;
+    }
+}
\ No newline at end of file
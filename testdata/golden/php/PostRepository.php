@@ -0,0 +1,84 @@
+<?php
+namespace Quaff;
+use DateTime;
+
+class PostRepository {
+    private const TABLE = '"posts"';
+    private const COLUMNS = ['id', 'author_id', 'title', 'body', 'meta', 'views', 'published', 'published_at'];
+
+    public function __construct(private \PDO $pdo) { }
+
+    public function find(int $id): ?Post {
+        $stmt = $this->pdo->prepare('SELECT * FROM ' . self::TABLE . ' WHERE "id" = :pk__id');
+        $stmt->execute(['pk__id' => $id]);
+        $row = $stmt->fetch(\PDO::FETCH_ASSOC);
+
+        return $row === false ? null : Post::fromRow($row);
+    }
+
+    /**
+     * @param array<string, mixed> $criteria Column values to match, joined with AND.
+     * @return Post[]
+     */
+    public function findBy(array $criteria): array {
+        $where = [];
+        $params = [];
+        foreach ($criteria as $column => $value) {
+            if (!in_array($column, self::COLUMNS, true)) {
+                throw new \InvalidArgumentException("Unknown column: {$column}");
+            }
+            if ($value === null) {
+                $where[] = self::quote($column) . ' IS NULL';
+            } else {
+                $where[] = self::quote($column) . ' = :' . $column;
+                $params[$column] = $value;
+            }
+        }
+
+        $sql = 'SELECT * FROM ' . self::TABLE;
+        if ($where !== []) {
+            $sql .= ' WHERE ' . implode(' AND ', $where);
+        }
+
+        $stmt = $this->pdo->prepare($sql);
+        $stmt->execute($params);
+
+        return array_map(
+            fn (array $row): Post => Post::fromRow($row),
+            $stmt->fetchAll(\PDO::FETCH_ASSOC),
+        );
+    }
+
+    /** @return mixed The primary key of the inserted row. */
+    public function insert(Post $model): mixed {
+        $row = $model->toInsertArray();
+        $columns = array_map(fn (string $column): string => self::quote($column), array_keys($row));
+        $placeholders = array_map(fn (string $column): string => ':' . $column, array_keys($row));
+
+        $stmt = $this->pdo->prepare(
+            'INSERT INTO ' . self::TABLE . ' (' . implode(', ', $columns) . ') VALUES (' . implode(', ', $placeholders) . ')'
+        );
+        $stmt->execute($row);
+
+        return $this->pdo->lastInsertId();
+    }
+
+    public function update(Post $model): bool {
+        $row = $model->toInsertArray();
+        $sets = array_map(fn (string $column): string => self::quote($column) . ' = :' . $column, array_keys($row));
+
+        $stmt = $this->pdo->prepare('UPDATE ' . self::TABLE . ' SET ' . implode(', ', $sets) . ' WHERE "id" = :pk__id');
+
+        return $stmt->execute($row + ['pk__id' => $model->getId()]);
+    }
+
+    public function delete(int $id): bool {
+        $stmt = $this->pdo->prepare('DELETE FROM ' . self::TABLE . ' WHERE "id" = :pk__id');
+
+        return $stmt->execute(['pk__id' => $id]);
+    }
+
+    private static function quote(string $identifier): string {
+        return '"' . str_replace('"', '""', $identifier) . '"';
+    }
+}
\ No newline at end of file
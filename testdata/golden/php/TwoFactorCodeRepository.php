@@ -0,0 +1,84 @@
+<?php
+namespace Quaff;
+use DateTime;
+
+class TwoFactorCodeRepository {
+    private const TABLE = '"two_factor_codes"';
+    private const COLUMNS = ['code', 'user_id'];
+
+    public function __construct(private \PDO $pdo) { }
+
+    public function find(string $code): ?TwoFactorCode {
+        $stmt = $this->pdo->prepare('SELECT * FROM ' . self::TABLE . ' WHERE "code" = :pk__code');
+        $stmt->execute(['pk__code' => $code]);
+        $row = $stmt->fetch(\PDO::FETCH_ASSOC);
+
+        return $row === false ? null : TwoFactorCode::fromRow($row);
+    }
+
+    /**
+     * @param array<string, mixed> $criteria Column values to match, joined with AND.
+     * @return TwoFactorCode[]
+     */
+    public function findBy(array $criteria): array {
+        $where = [];
+        $params = [];
+        foreach ($criteria as $column => $value) {
+            if (!in_array($column, self::COLUMNS, true)) {
+                throw new \InvalidArgumentException("Unknown column: {$column}");
+            }
+            if ($value === null) {
+                $where[] = self::quote($column) . ' IS NULL';
+            } else {
+                $where[] = self::quote($column) . ' = :' . $column;
+                $params[$column] = $value;
+            }
+        }
+
+        $sql = 'SELECT * FROM ' . self::TABLE;
+        if ($where !== []) {
+            $sql .= ' WHERE ' . implode(' AND ', $where);
+        }
+
+        $stmt = $this->pdo->prepare($sql);
+        $stmt->execute($params);
+
+        return array_map(
+            fn (array $row): TwoFactorCode => TwoFactorCode::fromRow($row),
+            $stmt->fetchAll(\PDO::FETCH_ASSOC),
+        );
+    }
+
+    /** @return string The primary key of the inserted row. */
+    public function insert(TwoFactorCode $model): string {
+        $row = $model->toInsertArray();
+        $columns = array_map(fn (string $column): string => self::quote($column), array_keys($row));
+        $placeholders = array_map(fn (string $column): string => ':' . $column, array_keys($row));
+
+        $stmt = $this->pdo->prepare(
+            'INSERT INTO ' . self::TABLE . ' (' . implode(', ', $columns) . ') VALUES (' . implode(', ', $placeholders) . ')'
+        );
+        $stmt->execute($row);
+
+        return $model->getCode();
+    }
+
+    public function update(TwoFactorCode $model): bool {
+        $row = $model->toInsertArray();
+        $sets = array_map(fn (string $column): string => self::quote($column) . ' = :' . $column, array_keys($row));
+
+        $stmt = $this->pdo->prepare('UPDATE ' . self::TABLE . ' SET ' . implode(', ', $sets) . ' WHERE "code" = :pk__code');
+
+        return $stmt->execute($row + ['pk__code' => $model->getCode()]);
+    }
+
+    public function delete(string $code): bool {
+        $stmt = $this->pdo->prepare('DELETE FROM ' . self::TABLE . ' WHERE "code" = :pk__code');
+
+        return $stmt->execute(['pk__code' => $code]);
+    }
+
+    private static function quote(string $identifier): string {
+        return '"' . str_replace('"', '""', $identifier) . '"';
+    }
+}
\ No newline at end of file
@@ -0,0 +1,73 @@
+<?php
+namespace Quaff;
+use DateTime;
+
+class Post {
+    public function __construct(
+        /** @var int BIGINT */
+        public int $author_id,
+        /** @var string VARCHAR(200) */
+        public string $title,
+        /** @var string|null TEXT */
+        public ?string $body,
+        /** @var array|null JSON */
+        public ?array $meta,
+        /** @var DateTime|null TIMESTAMP */
+        public ?DateTime $published_at,
+        /** @var int|null INT */
+        private readonly ?int $id = null,
+        /** @var int INT UNSIGNED */
+        public int $views = 0,
+        /** @var bool BOOLEAN */
+        public bool $published = false
+    ) {
+        if (mb_strlen($this->title) > 200) {
+            throw new \InvalidArgumentException('title must be at most 200 characters');
+        }
+        if ($this->views < 0) {
+            throw new \InvalidArgumentException('views must not be negative');
+        }
+    }
+
+    /** @param array<string, mixed> $row */
+    public static function fromRow(array $row): self {
+        return new self(
+            id: (int) $row['id'],
+            author_id: (int) $row['author_id'],
+            title: $row['title'],
+            body: $row['body'],
+            meta: $row['meta'] === null ? null : json_decode($row['meta'], true),
+            views: (int) $row['views'],
+            published: (bool) $row['published'],
+            published_at: $row['published_at'] === null ? null : new DateTime($row['published_at']),
+        );
+    }
+
+    public function getId(): ?int {
+        return $this->id;
+    }
+
+    public function toInsertArray(): array {
+        $row = [
+            'author_id' => $this->author_id,
+            'title' => $this->title,
+            'body' => $this->body,
+            'meta' => $this->meta === null ? null : json_encode($this->meta),
+            'views' => $this->views,
+            'published' => (int) $this->published,
+            'published_at' => $this->published_at === null ? null : $this->published_at->format('Y-m-d H:i:s'),
+        ];
+
+        return $row;
+    }
+
+    /** @return string[] */
+    public function validate(): array {
+        $violations = [];
+        if (!($this->published || $this->published_at === null)) {
+            $violations[] = 'Check constraint failed: published OR published_at IS NULL';
+        }
+
+        return $violations;
+    }
+}
\ No newline at end of file
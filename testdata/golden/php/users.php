@@ -0,0 +1,82 @@
+<?php
+namespace Quaff;
+use DateTime;
+
+/** People who log in */
+class User {
+    public function __construct(
+        /**
+         * Login e-mail, never *\/ shared
+         *
+         * @var string VARCHAR(100)
+         */
+        public string $email,
+        /**
+         * Multi
+         * line "quoted" $x <b>
+         *
+         * @var string|null TEXT
+         */
+        public ?string $class,
+        /** @var string|null DECIMAL(8,2) */
+        public ?string $price,
+        /** @var string|null UUID */
+        public ?string $token,
+        /** @var int|null BIGINT */
+        private readonly ?int $id = null,
+        /** @var string ENUM('active', 'banned') */
+        public string $status = 'active',
+        /** @var bool BOOLEAN */
+        public bool $active = true,
+        /** @var DateTime|null TIMESTAMP */
+        public ?DateTime $created_at = null
+    ) {
+        if (mb_strlen($this->email) > 100) {
+            throw new \InvalidArgumentException('email must be at most 100 characters');
+        }
+    }
+
+    /** @param array<string, mixed> $row */
+    public static function fromRow(array $row): self {
+        return new self(
+            id: (int) $row['id'],
+            email: $row['email'],
+            class: $row['class'],
+            price: $row['price'],
+            status: $row['status'],
+            active: (bool) $row['active'],
+            token: $row['token'],
+            created_at: new DateTime($row['created_at']),
+        );
+    }
+
+    public function getId(): ?int {
+        return $this->id;
+    }
+
+    public function toInsertArray(): array {
+        $row = [
+            'email' => $this->email,
+            'class' => $this->class,
+            'price' => $this->price,
+            'status' => $this->status,
+            'active' => (int) $this->active,
+            'token' => $this->token,
+        ];
+        if ($this->created_at !== null) {
+            $row['created_at'] = $this->created_at->format('Y-m-d H:i:s');
+        }
+
+        return $row;
+    }
+
+    /** @return string[] */
+    public function validate(): array {
+        $violations = [];
+        if (!($this->price === null || $this->price > 0)) {
+            $violations[] = 'Check constraint failed: price > 0';
+        }
+
+        return $violations;
+    }
+}
\ No newline at end of file
@@ -0,0 +1,9 @@
+# Generated by quaff from 2fa-codes.sql.
+
+from dataclasses import dataclass
+
+
+@dataclass
+class TwoFactorCode:
+    code: str
+    user_id: int
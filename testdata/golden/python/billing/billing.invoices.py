@@ -0,0 +1,14 @@
+# Generated by quaff from billing.invoices.sql.
+
+from dataclasses import dataclass
+from datetime import date
+from decimal import Decimal
+from typing import Optional
+
+
+@dataclass
+class Invoice:
+    id: int
+    user_id: Optional[int]
+    total: Decimal
+    issued_on: date
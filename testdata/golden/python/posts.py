@@ -0,0 +1,17 @@
+# Generated by quaff from posts.sql.
+
+from dataclasses import dataclass
+from datetime import datetime
+from typing import Any, Optional
+
+
+@dataclass
+class Post:
+    id: int
+    author_id: int
+    title: str
+    body: Optional[str]
+    meta: Optional[Any]
+    views: int
+    published: bool
+    published_at: Optional[datetime]
@@ -0,0 +1,30 @@
+# Generated by quaff from users.sql.
+
+from dataclasses import dataclass, field
+from datetime import datetime
+from decimal import Decimal
+from enum import Enum
+from typing import Optional
+from uuid import UUID
+
+
+class UserStatus(str, Enum):
+    ACTIVE = "active"
+    BANNED = "banned"
+
+
+@dataclass
+class User:
+    """People who log in"""
+
+    id: int
+    # Login e-mail, never */ shared
+    email: str
+    # Multi
+    # line "quoted" $x <b>
+    class_: Optional[str] = field(metadata={"column": "class"})
+    price: Optional[Decimal]
+    status: UserStatus
+    active: bool
+    token: Optional[UUID]
+    created_at: datetime
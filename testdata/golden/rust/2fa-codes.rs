@@ -0,0 +1,7 @@
+// Generated by quaff from 2fa-codes.sql.
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, sqlx::FromRow)]
+pub struct TwoFactorCode {
+    pub code: String,
+    pub user_id: i64,
+}
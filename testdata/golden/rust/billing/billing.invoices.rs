@@ -0,0 +1,9 @@
+// Generated by quaff from billing.invoices.sql.
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, sqlx::FromRow)]
+pub struct Invoice {
+    pub id: i32,
+    pub user_id: Option<i64>,
+    pub total: rust_decimal::Decimal,
+    pub issued_on: chrono::NaiveDate,
+}
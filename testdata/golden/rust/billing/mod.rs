@@ -0,0 +1,4 @@
+// Generated by quaff.
+
+#[path = "billing.invoices.rs"]
+pub mod billing_invoices;
@@ -0,0 +1,7 @@
+// Generated by quaff.
+
+#[path = "2fa-codes.rs"]
+pub mod _2fa_codes;
+pub mod billing;
+pub mod posts;
+pub mod users;
@@ -0,0 +1,13 @@
+// Generated by quaff from posts.sql.
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, sqlx::FromRow)]
+pub struct Post {
+    pub id: i32,
+    pub author_id: i64,
+    pub title: String,
+    pub body: Option<String>,
+    pub meta: Option<serde_json::Value>,
+    pub views: u32,
+    pub published: bool,
+    pub published_at: Option<chrono::NaiveDateTime>,
+}
@@ -0,0 +1,17 @@
+// Generated by quaff from users.sql.
+
+/// People who log in
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, sqlx::FromRow)]
+pub struct User {
+    pub id: i64,
+    /// Login e-mail, never */ shared
+    pub email: String,
+    /// Multi
+    /// line "quoted" $x <b>
+    pub class: Option<String>,
+    pub price: Option<rust_decimal::Decimal>,
+    pub status: String,
+    pub active: bool,
+    pub token: Option<uuid::Uuid>,
+    pub created_at: chrono::NaiveDateTime,
+}
@@ -0,0 +1,6 @@
+// Generated by quaff from 2fa-codes.sql.
+
+export interface TwoFactorCode {
+  code: string;
+  user_id: number;
+}
@@ -0,0 +1,8 @@
+// Generated by quaff from billing.invoices.sql.
+
+export interface Invoice {
+  id: number;
+  user_id: number | null;
+  total: string;
+  issued_on: string;
+}
@@ -0,0 +1,12 @@
+// Generated by quaff from posts.sql.
+
+export interface Post {
+  id: number;
+  author_id: number;
+  title: string;
+  body: string | null;
+  meta: unknown;
+  views: number;
+  published: boolean;
+  published_at: string | null;
+}
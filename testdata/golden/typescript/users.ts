@@ -0,0 +1,18 @@
+// Generated by quaff from users.sql.
+
+/** People who log in */
+export interface User {
+  id: number;
+  /** Login e-mail, never *\/ shared */
+  email: string;
+  /**
+   * Multi
+   * line "quoted" $x <b>
+   */
+  class: string | null;
+  price: string | null;
+  status: 'active' | 'banned';
+  active: boolean;
+  token: string | null;
+  created_at: string;
+}
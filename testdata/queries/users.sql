@@ -0,0 +1,13 @@
+-- name: GetUserByEmail :one
+SELECT * FROM users WHERE email = ?;
+
+-- name: ListPosts :many
+SELECT p.id, p.title, u.email AS author_email
+FROM posts p JOIN users u ON u.id = p.author_id
+WHERE p.published = :published LIMIT :limit;
+
+-- name: CreateUser :execlastid
+INSERT INTO users (email, status) VALUES (?, ?);
+
+-- name: Deactivate :execrows
+UPDATE users SET active = FALSE WHERE id = ?;
@@ -0,0 +1 @@
+CREATE TABLE two_factor_codes (code CHAR(6) PRIMARY KEY, user_id BIGINT NOT NULL);
@@ -0,0 +1,6 @@
+CREATE TABLE billing.invoices (
+  id INT PRIMARY KEY,
+  user_id BIGINT REFERENCES users (id),
+  total DECIMAL(10,2) NOT NULL,
+  issued_on DATE NOT NULL
+);
@@ -0,0 +1,11 @@
+CREATE TABLE posts (
+  id INT AUTO_INCREMENT PRIMARY KEY,
+  author_id BIGINT NOT NULL REFERENCES users (id),
+  title VARCHAR(200) NOT NULL,
+  body TEXT,
+  meta JSON,
+  views INT UNSIGNED NOT NULL DEFAULT 0,
+  published BOOLEAN NOT NULL DEFAULT FALSE,
+  published_at TIMESTAMP,
+  CHECK (published OR published_at IS NULL)
+);
@@ -0,0 +1,11 @@
+CREATE TABLE users (
+  id BIGINT AUTO_INCREMENT PRIMARY KEY,
+  email VARCHAR(100) NOT NULL UNIQUE COMMENT 'Login e-mail, never */ shared',
+  "class" TEXT COMMENT 'Multi
+line "quoted" $x <b>',
+  price DECIMAL(8,2) CHECK (price > 0),
+  status ENUM('active', 'banned') NOT NULL DEFAULT 'active',
+  active BOOLEAN NOT NULL DEFAULT TRUE,
+  token UUID,
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+) COMMENT = 'People who log in';